@@ -0,0 +1,329 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    braced,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Expr, Ident, LitStr, Token,
+};
+
+/// `$crate` path of the calling crate followed by the tree.
+pub struct Input {
+    krate: TokenTree,
+    root: NodeDef,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let root = input.parse()?;
+
+        if !input.is_empty() {
+            return Err(input
+                .error("expected a single root node, wrap the nodes in a `sequence` or `select`"));
+        }
+
+        Ok(Self { krate, root })
+    }
+}
+
+/// `kind "name"? (args)? { children }? (else { children })?`
+struct NodeDef {
+    kind: Ident,
+    name: Option<LitStr>,
+    args: Option<(token::Paren, Punctuated<Expr, Token![,]>)>,
+    body: Option<(token::Brace, TokenStream)>,
+    negative: Option<(Token![else], token::Brace, TokenStream)>,
+}
+
+impl Parse for NodeDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Ident::peek_any) {
+            return Err(input.error("expected a node like `kind \"name\"? (args)? { children }?`"));
+        }
+
+        let kind = input.call(Ident::parse_any)?;
+        let name = if input.peek(LitStr) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        let args = if input.peek(token::Paren) {
+            let content;
+            let paren = parenthesized!(content in input);
+            Some((paren, content.parse_terminated(Expr::parse, Token![,])?))
+        } else {
+            None
+        };
+
+        let body = if input.peek(token::Brace) {
+            let content;
+            let brace = braced!(content in input);
+            Some((brace, content.parse()?))
+        } else {
+            None
+        };
+
+        let negative = if input.peek(Token![else]) {
+            let else_token = input.parse()?;
+            let content;
+            let brace = braced!(content in input);
+            Some((else_token, brace, content.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            kind,
+            name,
+            args,
+            body,
+            negative,
+        })
+    }
+}
+
+struct Children(Vec<NodeDef>);
+
+impl Parse for Children {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let nodes = Punctuated::<NodeDef, Token![,]>::parse_terminated(input)?;
+        Ok(Self(nodes.into_iter().collect()))
+    }
+}
+
+const KINDS: &[(&str, &str)] = &[
+    ("sequence", "sequence \"name\"? { children }"),
+    ("select", "select \"name\"? { children }"),
+    ("wait", "wait(seconds)"),
+    ("random_wait", "random_wait(max_seconds)"),
+    ("action", "action(name, fn)"),
+    ("action_success", "action_success(name, fn)"),
+    ("fallible_action", "fallible_action(name, fn)"),
+    ("stateful", "stateful(name, action)"),
+    ("routine", "routine(name, routine)"),
+    ("async_action", "async_action(name, factory)"),
+    (
+        "port_action",
+        "port_action(name, action, [(port, key), ..]?)",
+    ),
+    ("cond", "cond(name, fn) { positive } else { negative }"),
+    ("while", "while(name, condition) { child }"),
+    (
+        "observe",
+        "observe(name, [keys], aborts, condition) { child }",
+    ),
+    ("node", "node(expression)"),
+];
+
+pub fn expand(input: Input) -> syn::Result<TokenStream> {
+    expand_node(&input.krate, input.root)
+}
+
+fn expand_node(krate: &TokenTree, node: NodeDef) -> syn::Result<TokenStream> {
+    let kind = node.kind.to_string();
+    let usage = match KINDS.iter().find(|(k, _)| *k == kind) {
+        Some((_, usage)) => *usage,
+        None => return Err(unknown_kind(&node.kind)),
+    };
+
+    let composite = kind == "sequence" || kind == "select";
+
+    if let (Some(name), false) = (&node.name, composite) {
+        return Err(expected(name.span(), usage));
+    }
+
+    if let (Some((_, brace, _)), false) = (&node.negative, kind == "cond") {
+        return Err(expected(brace.span.join(), usage));
+    }
+
+    let args = match (&node.args, composite) {
+        (Some((paren, _)), true) => return Err(expected(paren.span.join(), usage)),
+        (Some((paren, args)), false) => {
+            let (min, max) = match kind.as_str() {
+                "wait" | "random_wait" | "node" => (1, 1),
+                "port_action" => (2, 3),
+                "observe" => (4, 4),
+                _ => (2, 2),
+            };
+
+            if args.len() < min || args.len() > max {
+                return Err(expected(paren.span.join(), usage));
+            }
+
+            args.iter().collect::<Vec<_>>()
+        }
+        (None, true) => vec![],
+        (None, false) => return Err(expected(node.kind.span(), usage)),
+    };
+
+    let takes_children = composite || ["cond", "while", "observe"].contains(&kind.as_str());
+
+    let body = match (node.body, takes_children) {
+        (Some((brace, _)), false) => return Err(expected(brace.span.join(), usage)),
+        (Some(body), true) => Some(body),
+        (None, true) => {
+            let span = node
+                .args
+                .as_ref()
+                .map_or(node.kind.span(), |(paren, _)| paren.span.join());
+            return Err(expected(span, usage));
+        }
+        (None, false) => None,
+    };
+
+    let tokens = match kind.as_str() {
+        "sequence" | "select" => {
+            let children = syn::parse2::<Children>(body.unwrap().1)?
+                .0
+                .into_iter()
+                .map(|child| expand_node(krate, child))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            let (plain, named) = if kind == "sequence" {
+                (quote!(sequence), quote!(named_sequence))
+            } else {
+                (quote!(select), quote!(named_select))
+            };
+
+            match node.name {
+                Some(name) => quote! { #krate::Node::#named(#name, vec![#(#children),*]) },
+                None => quote! { #krate::Node::#plain(vec![#(#children),*]) },
+            }
+        }
+        "wait" | "random_wait" | "action" | "action_success" | "fallible_action" | "routine"
+        | "async_action" => {
+            let constructor = &node.kind;
+            quote! { #krate::Node::#constructor(#(#args),*) }
+        }
+        "stateful" => {
+            let (name, action) = (args[0], args[1]);
+            quote! { #krate::Node::stateful_action(#name, ::std::boxed::Box::new(#action)) }
+        }
+        "port_action" => {
+            let (name, action) = (args[0], args[1]);
+            let remaps = match args.get(2) {
+                Some(remaps) => quote!(#remaps),
+                None => quote!([]),
+            };
+            quote! { #krate::Node::port_action(#name, #action, &#remaps) }
+        }
+        "cond" => {
+            let body = body.unwrap();
+            let (_, brace, negative) = match node.negative {
+                Some(negative) => negative,
+                None => {
+                    return Err(syn::Error::new(
+                        body.0.span.close(),
+                        "expected `else { negative }` after the positive branch of `cond`",
+                    ))
+                }
+            };
+
+            let (name, cond) = (args[0], args[1]);
+            let positive = single_child(krate, body, &kind)?;
+            let negative = single_child(krate, (brace, negative), &kind)?;
+
+            quote! { #krate::Node::cond(#name, #cond, #positive, #negative) }
+        }
+        "while" => {
+            let (name, cond) = (args[0], args[1]);
+            let child = single_child(krate, body.unwrap(), &kind)?;
+            quote! {
+                #krate::Node::named_while_single(#name, ::std::boxed::Box::new(#cond), #child)
+            }
+        }
+        "observe" => {
+            let (name, keys, aborts, cond) = (args[0], args[1], args[2], args[3]);
+            let child = single_child(krate, body.unwrap(), &kind)?;
+            quote! {
+                #krate::Node::observe(#name, &#keys, #aborts, ::std::boxed::Box::new(#cond), #child)
+            }
+        }
+        "node" => {
+            let node = args[0];
+            quote! { #node }
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(tokens)
+}
+
+/// Branches of `cond`, `while` and `observe` take exactly one node.
+fn single_child(
+    krate: &TokenTree,
+    (brace, body): (token::Brace, TokenStream),
+    kind: &str,
+) -> syn::Result<TokenStream> {
+    let mut children = syn::parse2::<Children>(body)?.0;
+
+    match children.len() {
+        1 => expand_node(krate, children.remove(0)),
+        0 => Err(syn::Error::new(
+            brace.span.join(),
+            format!("`{}` takes a single child node, found none", kind),
+        )),
+        _ => Err(syn::Error::new(
+            children[1].kind.span(),
+            format!(
+                "`{}` takes a single child node, wrap them in a `sequence` or `select`",
+                kind
+            ),
+        )),
+    }
+}
+
+fn expected(span: Span, usage: &str) -> syn::Error {
+    syn::Error::new(span, format!("expected `{}`", usage))
+}
+
+fn unknown_kind(kind: &Ident) -> syn::Error {
+    let name = kind.to_string();
+    let message = match KINDS
+        .iter()
+        .map(|(k, _)| *k)
+        .min_by_key(|k| distance(&name, k))
+        .filter(|k| distance(&name, k) <= 2)
+    {
+        Some(closest) => format!("unknown node kind `{}`, did you mean `{}`?", name, closest),
+        None => format!(
+            "unknown node kind `{}`, expected one of {}",
+            name,
+            KINDS
+                .iter()
+                .map(|(k, _)| format!("`{}`", k))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    syn::Error::new(kind.span(), message)
+}
+
+/// Levenshtein distance, for suggesting the closest kind.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(row[j + 1])
+            };
+            previous = current;
+        }
+    }
+
+    row[b.len()]
+}
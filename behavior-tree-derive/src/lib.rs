@@ -0,0 +1,191 @@
+//! Derive macros for the `behavior-tree` crate, use them through its
+//! `derive` feature rather than depending on this crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, Meta, Type};
+
+mod bt;
+
+/// Implementation of `behavior_tree::bt!`, which passes its `$crate` path
+/// followed by `;` before the tree.
+#[doc(hidden)]
+#[proc_macro]
+pub fn bt(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as bt::Input);
+
+    match bt::expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `StatefulAction` for a struct with an inherent
+/// `fn tick(&mut self, data: &mut T) -> Status` method.
+///
+/// `reset()` assigns the initial value given by `#[reset = literal]` or
+/// `#[reset(expr)]` to each annotated field, or `Default::default()` for a
+/// bare `#[reset]`. Fields without the attribute are considered configuration
+/// and are kept as they are.
+///
+/// Also generates a `fn node(self) -> Node<T>` constructor, naming the node
+/// after the type in snake case unless `#[action(name = "..")]` is given.
+/// The action is implemented for any context unless it is restricted with
+/// `#[action(context = Type)]`. `#[action(clone)]` implements `box_clone()`
+/// for types that are `Clone`, allowing trees using them to be copied with
+/// `Node::clone_tree`.
+#[proc_macro_derive(StatefulAction, attributes(action, reset))]
+pub fn derive_stateful_action(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+
+    let mut name = None;
+    let mut context: Option<Type> = None;
+    let mut clone = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("action"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("context") {
+                context = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("clone") {
+                clone = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"..\"`, `context = Type` or `clone`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => vec![],
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    data.fields.span(),
+                    "StatefulAction can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "StatefulAction can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut resets = vec![];
+
+    for field in fields {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("reset"))
+        {
+            let value: Expr = match &attr.meta {
+                Meta::Path(_) => syn::parse_quote!(::core::default::Default::default()),
+                Meta::NameValue(meta) => meta.value.clone(),
+                Meta::List(list) => list.parse_args()?,
+            };
+
+            let field_ident = &field.ident;
+            resets.push(quote! { self.#field_ident = #value; });
+        }
+    }
+
+    let name = name.unwrap_or_else(|| snake_case(&ident.to_string()));
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut generics = input.generics.clone();
+    let context = match context {
+        Some(context) => context,
+        None => {
+            let param = syn::Ident::new("__BehaviorTreeContext", Span::call_site());
+            generics.params.push(syn::parse_quote!(#param));
+            syn::parse_quote!(#param)
+        }
+    };
+    let box_clone = if clone {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(Self: ::core::clone::Clone + 'static));
+
+        quote! {
+            fn box_clone(
+                &self,
+            ) -> ::core::option::Option<::std::boxed::Box<dyn ::behavior_tree::StatefulAction<#context>>> {
+                ::core::option::Option::Some(::std::boxed::Box::new(::core::clone::Clone::clone(self)))
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let (node_impl_generics, _, node_where_clause) = input.generics.split_for_impl();
+    let node_generics = if generics.params.len() > input.generics.params.len() {
+        quote! { <#context> }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::behavior_tree::StatefulAction<#context> for #ident #ty_generics #where_clause {
+            // Without an inherent `tick` this would call itself.
+            #[deny(unconditional_recursion)]
+            fn tick(&mut self, data: &mut #context) -> ::behavior_tree::Status {
+                Self::tick(self, data)
+            }
+
+            fn reset(&mut self) {
+                #(#resets)*
+            }
+
+            #box_clone
+        }
+
+        impl #node_impl_generics #ident #ty_generics #node_where_clause {
+            pub fn node #node_generics (self) -> ::behavior_tree::Node<#context>
+            where
+                Self: ::behavior_tree::StatefulAction<#context> + 'static,
+            {
+                ::behavior_tree::Node::stateful_action(#name, ::std::boxed::Box::new(self))
+            }
+        }
+    })
+}
+
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,136 @@
+//! Reference client for `DebugServer`, printing everything the server sends
+//! and turning lines typed on stdin into commands:
+//!
+//! ```text
+//! cargo run --example debug_client --features debug-server -- 127.0.0.1:7777
+//!
+//! tree | pause | resume | step
+//! break <path> [status] | clear <path>
+//! force <path> <status> | skip <path> | unforce <path>
+//! ```
+use behavior_tree::*;
+use std::io::BufRead;
+
+fn parse_status(status: &str) -> Option<Status> {
+    match status.to_lowercase().as_str() {
+        "success" => Some(Status::Success),
+        "failure" => Some(Status::Failure),
+        "running" => Some(Status::Running),
+        _ => None,
+    }
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let path = || words.get(1).map(|p| p.to_string()).ok_or("missing path");
+    let status = || match words.get(2) {
+        Some(status) => parse_status(status)
+            .map(Some)
+            .ok_or_else(|| format!("unknown status {}", status)),
+        None => Ok(None),
+    };
+
+    Ok(match words.first().copied() {
+        Some("tree") => Command::GetTree,
+        Some("pause") => Command::Pause,
+        Some("resume") => Command::Resume,
+        Some("step") => Command::Step,
+        Some("break") => Command::Breakpoint {
+            path: path()?,
+            status: status()?,
+        },
+        Some("clear") => Command::ClearBreakpoint { path: path()? },
+        Some("force") => Command::Force {
+            path: path()?,
+            status: Some(status()?.ok_or("missing status")?),
+        },
+        Some("skip") => Command::Skip { path: path()? },
+        Some("unforce") => Command::Force {
+            path: path()?,
+            status: None,
+        },
+        _ => return Err(format!("unknown command: {}", line)),
+    })
+}
+
+fn print(message: &Message) {
+    match message {
+        Message::Tree {
+            tick,
+            paused,
+            nodes,
+        } => {
+            println!(
+                "tree at tick {}{}",
+                tick,
+                if *paused { " (paused)" } else { "" }
+            );
+
+            for node in nodes {
+                let depth = node.path.matches('/').count();
+                let forced = match node.forced {
+                    Some(Override::Status(status)) => format!(" forced {:?}", status),
+                    Some(Override::Skip) => " skipped".to_owned(),
+                    None => String::new(),
+                };
+
+                println!(
+                    "{:>6} {}{} [{:?}]{}",
+                    node.id.to_string(),
+                    "  ".repeat(depth),
+                    node.name,
+                    node.status,
+                    forced
+                );
+            }
+        }
+        Message::Tick {
+            tick,
+            status,
+            changes,
+        } => {
+            let changes: Vec<String> = changes
+                .iter()
+                .map(|change| format!("{}={:?}", change.id, change.status))
+                .collect();
+            println!("tick {} {:?} {}", tick, status, changes.join(" "));
+        }
+        Message::Paused { tick, breakpoint } => match breakpoint {
+            Some(path) => println!("paused at tick {} on breakpoint {}", tick, path),
+            None => println!("paused at tick {}", tick),
+        },
+        Message::Resumed { tick } => println!("resumed at tick {}", tick),
+        Message::Error { message } => eprintln!("error: {}", message),
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:7777".to_owned());
+
+    let mut client = DebugClient::connect(&addr)?;
+    let mut commands = DebugClient::connect(&addr)?;
+
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            match parse_command(&line) {
+                Ok(command) => {
+                    if commands.send(&command).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+    });
+
+    loop {
+        print(&client.recv()?);
+    }
+}
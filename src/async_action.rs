@@ -0,0 +1,133 @@
+use crate::prelude::*;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+type BoxedFuture = Pin<Box<dyn Future<Output = Status>>>;
+
+/// Conversion of the output of a future driven by [`AsyncAction`] into the
+/// status of the node.
+pub trait IntoStatus {
+    fn into_status(self) -> Status;
+}
+
+impl IntoStatus for () {
+    fn into_status(self) -> Status {
+        Status::Success
+    }
+}
+
+impl IntoStatus for bool {
+    fn into_status(self) -> Status {
+        if self {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+impl<V, E> IntoStatus for Result<V, E> {
+    fn into_status(self) -> Status {
+        self.is_ok().into_status()
+    }
+}
+
+/// Action backed by a future, which is created from the context the first
+/// time the node is ticked and then polled once per tick until it completes.
+/// Resetting the node drops the future, cancelling the work in progress.
+///
+/// By default the future is polled with a waker that does nothing, so the
+/// node needs to be ticked every update. A custom waker can be provided with
+/// [`AsyncAction::with_waker`], in which case the node doesn't ask for any
+/// wake-ups by itself and relies on the waker to notify the tree.
+pub struct AsyncAction<T> {
+    factory: Box<dyn FnMut(&mut T) -> BoxedFuture>,
+    future: Option<BoxedFuture>,
+    waker: Option<Waker>,
+}
+
+impl<T> AsyncAction<T> {
+    pub fn new<F, Fut>(mut factory: F) -> Self
+    where
+        F: FnMut(&mut T) -> Fut + 'static,
+        Fut: Future + 'static,
+        Fut::Output: IntoStatus,
+    {
+        Self {
+            factory: Box::new(move |data| {
+                let future = factory(data);
+                Box::pin(async move { future.await.into_status() })
+            }),
+            future: None,
+            waker: None,
+        }
+    }
+
+    pub fn with_waker(self, waker: Waker) -> Self {
+        Self {
+            waker: Some(waker),
+            ..self
+        }
+    }
+
+    /// Whether a future is currently in flight.
+    pub fn is_pending(&self) -> bool {
+        self.future.is_some()
+    }
+}
+
+impl<T> StatefulAction<T> for AsyncAction<T> {
+    fn tick(&mut self, data: &mut T) -> Status {
+        let factory = &mut self.factory;
+        let future = self.future.get_or_insert_with(|| factory(data));
+
+        let waker = self.waker.clone().unwrap_or_else(noop_waker);
+        let mut cx = Context::from_waker(&waker);
+
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(status) => {
+                self.future = None;
+                status
+            }
+            Poll::Pending => Status::Running,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.future = None;
+    }
+
+    fn wakeup(&self) -> Option<f64> {
+        match self.waker {
+            Some(_) => None,
+            None => Some(0.0),
+        }
+    }
+}
+
+impl<T: 'static> Node<T> {
+    pub fn async_action<F, Fut>(name: &str, factory: F) -> Node<T>
+    where
+        F: FnMut(&mut T) -> Fut + 'static,
+        Fut: Future + 'static,
+        Fut::Output: IntoStatus,
+    {
+        Self::stateful_action(name, Box::new(AsyncAction::new(factory)))
+    }
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    fn noop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // Safety: the vtable functions ignore the data pointer entirely.
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
@@ -1,21 +1,207 @@
+#[cfg(feature = "breakpoints")]
+use crate::breakpoint::Debugger;
 use crate::maybe_profile_function;
+use crate::path::NodeId;
 use crate::prelude::*;
-use std::{cell::RefCell, rc::Rc};
+use std::{any::Any, cell::RefCell, rc::Rc};
 
 pub trait StatefulAction<T> {
     fn tick(&mut self, data: &mut T) -> Status;
     fn reset(&mut self);
+
+    /// Called by the tree instead of `tick`, for actions that need to know
+    /// how much time passed since the previous tick.
+    fn tick_delta(&mut self, _delta: f64, data: &mut T) -> Status {
+        self.tick(data)
+    }
+
+    /// Seconds after which a running action wants to be ticked again when
+    /// the tree is driven by [`BehaviorTree::update`], `None` meaning it only
+    /// needs a tick once the host calls [`BehaviorTree::notify`]. Defaults to
+    /// being ticked every update.
+    fn wakeup(&self) -> Option<f64> {
+        Some(0.0)
+    }
+
+    /// Copy of the action used by [`Node::clone_tree`], which resets it
+    /// afterwards. Actions returning `None` (the default) can't be cloned.
+    fn box_clone(&self) -> Option<Box<dyn StatefulAction<T>>> {
+        None
+    }
+
+    /// State handed to the new definition of this action by
+    /// [`BehaviorTree::hot_reload`], see `take_over_state`.
+    fn hand_over_state(&mut self) -> Option<Box<dyn Any>> {
+        None
+    }
+
+    /// Picks up the state handed over by the action this one replaces in a
+    /// hot reload. Returns `false` (the default) if it can't, in which case
+    /// the action starts from scratch.
+    fn take_over_state(&mut self, _state: Box<dyn Any>) -> bool {
+        false
+    }
+
+    /// Internal state to be stored in a [`NodeSnapshot`], actions that don't
+    /// return anything start over when the snapshot is restored.
+    #[cfg(feature = "persistence")]
+    fn save_state(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Restores state previously returned by `save_state`.
+    #[cfg(feature = "persistence")]
+    fn load_state(&mut self, _state: serde_json::Value) -> Result<(), serde_json::Error> {
+        Ok(())
+    }
 }
 
 pub struct BehaviorTree<T> {
     pub tree: Rc<RefCell<Node<T>>>,
+    /// Time accumulated by `update` since the last tick.
+    pub(crate) elapsed: f64,
+    pub(crate) notified: bool,
+    #[cfg(feature = "breakpoints")]
+    pub(crate) debugger: Debugger,
 }
 
 impl<T> BehaviorTree<T> {
     pub fn new(root: Node<T>) -> Self {
         let root = Rc::new(RefCell::new(root));
 
-        Self { tree: root }
+        let tree = Self {
+            tree: root,
+            elapsed: 0.0,
+            notified: false,
+            #[cfg(feature = "breakpoints")]
+            debugger: Debugger::default(),
+        };
+
+        tree.number_nodes();
+        tree
+    }
+
+    /// Ticks the whole tree unconditionally. Time accumulated by `update`
+    /// without ticking is added to `delta`.
+    pub fn tick(&mut self, delta: f64, context: &mut T) -> Status {
+        maybe_profile_function!();
+
+        let delta = self.elapsed + delta;
+        self.elapsed = 0.0;
+        self.notified = false;
+
+        // A tree ticked from inside another tree's `debug_tick` doesn't stop
+        // at its breakpoints.
+        #[cfg(feature = "breakpoints")]
+        let _suspended = crate::breakpoint::suspend();
+
+        self.tree.borrow_mut().tick(delta, context)
+    }
+
+    /// Like `tick`, but surfaces errors returned by fallible actions during
+    /// the tick. An action that errors still fails like any other, so the
+    /// rest of the tick proceeds normally, the error is only reported once
+    /// the tick is over. When several actions fail, the first one in depth
+    /// first order is returned and the rest are discarded. Errors left over
+    /// from a plain `tick` are never reported.
+    pub fn try_tick(&mut self, delta: f64, context: &mut T) -> Result<Status, TickError> {
+        let mut errors = vec![];
+        self.tree.borrow_mut().take_errors("root", &mut errors);
+        errors.clear();
+
+        let status = self.tick(delta, context);
+        self.tree.borrow_mut().take_errors("root", &mut errors);
+
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(status),
+        }
+    }
+
+    /// Event driven alternative to `tick`, which only ticks the tree when
+    /// something could have changed: it was notified, a running `Wait` ran
+    /// out or a running action asked for it. Returns `None` when the tick
+    /// was skipped.
+    pub fn update(&mut self, delta: f64, context: &mut T) -> Option<Status> {
+        self.elapsed += delta;
+
+        match self.next_wakeup() {
+            Some(wakeup) if wakeup <= 0.0 => Some(self.tick(0.0, context)),
+            _ => None,
+        }
+    }
+
+    /// Pins the node with the given id to `forced`, or lets it tick normally
+    /// again for `None`. Returns `false` if there is no such node.
+    pub fn force(&self, id: NodeId, forced: Option<Override>) -> bool {
+        match self.find_by_id(id) {
+            Some(node) => {
+                node.borrow_mut().forced = forced;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Like `force`, for a path such as `root/Sequence[2]/Attack`.
+    pub fn force_path(&self, path: &str, forced: Option<Override>) -> bool {
+        match self.find_by_path(path) {
+            Some(node) => {
+                node.borrow_mut().forced = forced;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_forced(&self) {
+        for (_, node) in self.nodes() {
+            node.borrow_mut().forced = None;
+        }
+    }
+
+    /// Requests a tick on the next `update`, typically after the host
+    /// changed something the tree depends on.
+    pub fn notify(&mut self) {
+        self.notified = true;
+    }
+
+    /// Seconds until the tree needs to be ticked again, `None` if it is idle
+    /// until notified.
+    pub fn next_wakeup(&self) -> Option<f64> {
+        let root = self.tree.borrow();
+
+        if self.notified || root.status == Status::Initialized {
+            return Some(0.0);
+        }
+
+        root.next_wakeup()
+            .map(|wakeup| (wakeup - self.elapsed).max(0.0))
+    }
+}
+
+/// Condition of a [`Behavior::Cond`], either a plain function or a closure
+/// (e.g. a compiled script).
+pub enum Condition<T> {
+    Fn(fn(&T) -> bool),
+    Closure(Rc<dyn Fn(&T) -> bool>),
+}
+
+impl<T> Condition<T> {
+    pub fn check(&self, data: &T) -> bool {
+        match self {
+            Condition::Fn(cond) => cond(data),
+            Condition::Closure(cond) => cond(data),
+        }
+    }
+}
+
+impl<T> Clone for Condition<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Condition::Fn(cond) => Condition::Fn(*cond),
+            Condition::Closure(cond) => Condition::Closure(cond.clone()),
+        }
     }
 }
 
@@ -31,10 +217,12 @@ pub enum Behavior<T> {
         max: f64,
     },
 
+    /// Ticks the first child while the condition holds and the second one
+    /// otherwise, resetting the branch it switched away from.
     Cond(
         String,
         // Rc<dyn Fn(&mut T, &P) -> bool>,
-        fn(&T) -> bool,
+        Condition<T>,
         Rc<RefCell<Node<T>>>,
         Rc<RefCell<Node<T>>>,
     ),
@@ -44,6 +232,14 @@ pub enum Behavior<T> {
 
     Action(String, fn(&mut T) -> Status),
     ActionSuccess(String, fn(&mut T) -> ()),
+    /// Action which can fail with an error, which is kept until it is
+    /// collected by [`BehaviorTree::try_tick`], the action is ticked again or
+    /// reset.
+    FallibleAction(
+        String,
+        fn(&mut T) -> Result<Status, ActionError>,
+        Option<ActionError>,
+    ),
 
     StatefulAction(String, Box<dyn StatefulAction<T>>),
     // StatefulAction(String, fn(&mut T, &P) -> Status),
@@ -53,7 +249,17 @@ pub enum Behavior<T> {
     // Condition(Rc<dyn Fn(f64, &mut T, &P) -> bool>, Rc<Behavior<T>>),
     // WaitForever,
     // Action(T),
-    While(Box<dyn Fn(&T) -> bool>, Rc<RefCell<Node<T>>>),
+    While(Rc<dyn Fn(&T) -> bool>, Rc<RefCell<Node<T>>>),
+
+    Observe {
+        cond: Rc<dyn Fn(&T) -> bool>,
+        /// Latest revision of the observed blackboard keys.
+        revision: Rc<dyn Fn(&T) -> u64>,
+        aborts: Aborts,
+        /// Revision at which `cond` was last evaluated and its result.
+        seen: Option<(u64, bool)>,
+        child: Rc<RefCell<Node<T>>>,
+    },
 }
 
 fn sequence<T>(
@@ -102,7 +308,12 @@ fn sequence<T>(
             // continue;
             x.reset();
         }
-        let res = x.tick(delta, context);
+        let mut res = x.tick(delta, context);
+
+        // Skipped children don't decide anything, unless they are pending.
+        if x.forced == Some(Override::Skip) && res != Status::Running {
+            res = status_positive;
+        }
 
         if res == status_positive {
             *current += 1;
@@ -193,13 +404,14 @@ impl<T> Behavior<T> {
             }
 
             Behavior::Cond(_, cond, a, b) => {
-                let c = cond(context);
+                let (taken, other) = if cond.check(context) { (a, b) } else { (b, a) };
 
-                let status = if c {
-                    a.borrow_mut().tick(delta, context)
-                } else {
-                    b.borrow_mut().tick(delta, context)
-                };
+                // The branch the condition moved away from doesn't stay running.
+                if other.borrow().status == Status::Running {
+                    other.borrow_mut().reset();
+                }
+
+                let status = taken.borrow_mut().tick(delta, context);
 
                 return status;
             }
@@ -222,9 +434,53 @@ impl<T> Behavior<T> {
                 return Status::Success;
             }
 
+            Behavior::FallibleAction(_, action, error) => match action(context) {
+                Ok(status) => {
+                    *error = None;
+                    return status;
+                }
+                Err(err) => {
+                    *error = Some(err);
+                    return Status::Failure;
+                }
+            },
+
             // TODO: state reset?
             Behavior::StatefulAction(_, action) => {
-                return action.tick(context);
+                return action.tick_delta(delta, context);
+            }
+
+            Behavior::Observe {
+                cond,
+                revision,
+                aborts,
+                seen,
+                child,
+            } => {
+                let mut child = child.borrow_mut();
+                let revision = revision(context);
+
+                let holds = match *seen {
+                    // Once the child is running only aborts can interrupt it.
+                    Some((_, holds)) if child.status == Status::Running && !aborts.itself() => {
+                        holds
+                    }
+                    Some((seen_revision, holds)) if seen_revision == revision => holds,
+                    _ => {
+                        let holds = cond(context);
+                        *seen = Some((revision, holds));
+                        holds
+                    }
+                };
+
+                if holds {
+                    return child.tick(delta, context);
+                } else {
+                    if child.status == Status::Running {
+                        child.reset();
+                    }
+                    return Status::Failure;
+                }
             }
 
             Behavior::While(cond, behavior) => {
@@ -272,6 +528,48 @@ impl<T> Behavior<T> {
         // (status, DebugRepr::new("X", status))
     }
 
+    /// Re-evaluates an observer condition if its keys changed since it was
+    /// last checked, returns `None` if nothing changed.
+    pub(crate) fn observe_changed(&mut self, context: &T) -> Option<bool> {
+        match self {
+            Behavior::Observe {
+                cond,
+                revision,
+                seen,
+                ..
+            } => {
+                let revision = revision(context);
+
+                match *seen {
+                    Some((seen_revision, _)) if seen_revision == revision => None,
+                    _ => {
+                        let holds = cond(context);
+                        *seen = Some((revision, holds));
+                        Some(holds)
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of the variant, without any of its data.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Behavior::Wait { .. } => "Wait",
+            Behavior::RandomWait { .. } => "RandomWait",
+            Behavior::Cond(..) => "Cond",
+            Behavior::Sequence(..) => "Sequence",
+            Behavior::Select(..) => "Select",
+            Behavior::Action(..) => "Action",
+            Behavior::ActionSuccess(..) => "ActionSuccess",
+            Behavior::FallibleAction(..) => "FallibleAction",
+            Behavior::StatefulAction(..) => "StatefulAction",
+            Behavior::While(..) => "While",
+            Behavior::Observe { .. } => "Observe",
+        }
+    }
+
     pub fn reset(&mut self) {
         maybe_profile_function!();
 
@@ -303,7 +601,10 @@ impl<T> Behavior<T> {
                 state.reset();
             }
 
+            Behavior::FallibleAction(_, _, error) => *error = None,
+
             Behavior::While(_, node) => node.borrow_mut().reset(),
+            Behavior::Observe { child, .. } => child.borrow_mut().reset(),
             _ => {}
         }
     }
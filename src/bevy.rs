@@ -0,0 +1,126 @@
+use crate::prelude::*;
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    resource::Resource,
+    world::{EntityWorldMut, World},
+};
+use bevy_time::Time;
+use std::collections::HashMap;
+
+pub type TreeBuilder = fn() -> Node<World>;
+
+/// Gives an entity a behavior tree, built by `build` the first time
+/// [`tick_behavior_trees`] runs for the entity. Trees aren't `Send`, so they
+/// live in the [`BehaviorTrees`] non-send resource rather than in the
+/// component itself. Removing the component or despawning the entity drops
+/// the tree.
+#[derive(Component)]
+pub struct BehaviorTreeComponent {
+    pub build: TreeBuilder,
+    /// Status returned by the latest tick.
+    pub status: Status,
+}
+
+impl BehaviorTreeComponent {
+    pub fn new(build: TreeBuilder) -> Self {
+        Self {
+            build,
+            status: Status::Initialized,
+        }
+    }
+}
+
+/// Trees of all entities with a [`BehaviorTreeComponent`].
+#[derive(Default)]
+pub struct BehaviorTrees {
+    trees: HashMap<Entity, BehaviorTree<World>>,
+}
+
+impl BehaviorTrees {
+    pub fn get(&self, entity: Entity) -> Option<&BehaviorTree<World>> {
+        self.trees.get(&entity)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut BehaviorTree<World>> {
+        self.trees.get_mut(&entity)
+    }
+}
+
+/// Entity whose tree is being ticked, available to actions for the duration
+/// of the tick.
+#[derive(Resource, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CurrentAgent(pub Entity);
+
+/// Access to the entity whose tree is being ticked from within actions,
+/// which get the whole `World` as their context.
+pub trait AgentWorld {
+    fn agent(&self) -> Entity;
+    fn agent_mut(&mut self) -> EntityWorldMut<'_>;
+}
+
+impl AgentWorld for World {
+    fn agent(&self) -> Entity {
+        self.resource::<CurrentAgent>().0
+    }
+
+    fn agent_mut(&mut self) -> EntityWorldMut<'_> {
+        let agent = self.agent();
+        self.entity_mut(agent)
+    }
+}
+
+/// Exclusive system ticking the tree of every entity with a
+/// [`BehaviorTreeComponent`] by the `Time` delta (zero without a `Time`
+/// resource).
+pub fn tick_behavior_trees(world: &mut World) {
+    let delta = world
+        .get_resource::<Time>()
+        .map_or(0.0, |time| time.delta_secs_f64());
+
+    let agents: Vec<(Entity, TreeBuilder)> = world
+        .query::<(Entity, &BehaviorTreeComponent)>()
+        .iter(world)
+        .map(|(entity, component)| (entity, component.build))
+        .collect();
+
+    let mut old_trees = world
+        .remove_non_send_resource::<BehaviorTrees>()
+        .unwrap_or_default()
+        .trees;
+    let mut trees = BehaviorTrees::default();
+
+    for (entity, build) in agents {
+        // An action of a previous agent might have despawned this one.
+        if world.get::<BehaviorTreeComponent>(entity).is_none() {
+            continue;
+        }
+
+        let mut tree = old_trees
+            .remove(&entity)
+            .unwrap_or_else(|| BehaviorTree::new(build()));
+
+        world.insert_resource(CurrentAgent(entity));
+        let status = tree.tick(delta, world);
+
+        if let Some(mut component) = world.get_mut::<BehaviorTreeComponent>(entity) {
+            component.status = status;
+            trees.trees.insert(entity, tree);
+        }
+    }
+
+    world.remove_resource::<CurrentAgent>();
+    world.insert_non_send_resource(trees);
+}
+
+/// Ticks behavior trees in `Update`, see [`tick_behavior_trees`]. Expects
+/// `Time` to be provided by another plugin, such as `TimePlugin`.
+pub struct BehaviorTreePlugin;
+
+impl Plugin for BehaviorTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_non_send_resource::<BehaviorTrees>()
+            .add_systems(Update, tick_behavior_trees);
+    }
+}
@@ -0,0 +1,291 @@
+use crate::prelude::*;
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+/// String-keyed store of arbitrarily typed values that can be used as (or
+/// embedded in) the context of a tree, so that actions don't need to know the
+/// concrete game struct they run against.
+///
+/// Every write bumps a global revision counter and records it for the
+/// written key, which is what [`Node::observe`] uses to only re-evaluate its
+/// condition when one of the keys it depends on has changed.
+#[derive(Default)]
+pub struct Blackboard {
+    entries: HashMap<String, Box<dyn Any>>,
+    revisions: HashMap<String, u64>,
+    revision: u64,
+}
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `value` under `key`, replacing any previous value regardless
+    /// of its type.
+    pub fn set<V: 'static>(&mut self, key: &str, value: V) {
+        self.touch(key);
+        self.entries.insert(key.to_owned(), Box::new(value));
+    }
+
+    /// Returns the value under `key` if it exists and has type `V`.
+    pub fn get<V: 'static>(&self, key: &str) -> Option<&V> {
+        self.entries.get(key).and_then(|value| value.downcast_ref())
+    }
+
+    /// Mutable access to a value, which counts as a change of `key` even if
+    /// the value is never written to.
+    pub fn get_mut<V: 'static>(&mut self, key: &str) -> Option<&mut V> {
+        if self.get::<V>(key).is_some() {
+            self.touch(key);
+        }
+
+        self.entries
+            .get_mut(key)
+            .and_then(|value| value.downcast_mut())
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        let removed = self.entries.remove(key).is_some();
+        if removed {
+            self.touch(key);
+        }
+        removed
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|key| key.as_str())
+    }
+
+    /// Revision of the last change to any key.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Revision of the last change to `key`, `0` if it was never written.
+    pub fn key_revision(&self, key: &str) -> u64 {
+        self.revisions.get(key).copied().unwrap_or(0)
+    }
+
+    /// Keys that were set, mutably borrowed or removed after `revision`.
+    pub fn changed_since(&self, revision: u64) -> impl Iterator<Item = &str> {
+        self.revisions
+            .iter()
+            .filter(move |(_, rev)| **rev > revision)
+            .map(|(key, _)| key.as_str())
+    }
+
+    fn touch(&mut self, key: &str) {
+        self.revision += 1;
+        self.revisions.insert(key.to_owned(), self.revision);
+    }
+}
+
+/// Implemented by contexts that carry a [`Blackboard`], which is what nodes
+/// with ports read from and write to.
+pub trait AsBlackboard {
+    fn blackboard(&self) -> &Blackboard;
+    fn blackboard_mut(&mut self) -> &mut Blackboard;
+}
+
+impl AsBlackboard for Blackboard {
+    fn blackboard(&self) -> &Blackboard {
+        self
+    }
+
+    fn blackboard_mut(&mut self) -> &mut Blackboard {
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PortDirection {
+    Input,
+    Output,
+    InOut,
+}
+
+impl PortDirection {
+    pub fn can_read(self) -> bool {
+        self != PortDirection::Output
+    }
+
+    pub fn can_write(self) -> bool {
+        self != PortDirection::Input
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Port {
+    pub name: &'static str,
+    pub direction: PortDirection,
+}
+
+impl Port {
+    pub fn input(name: &'static str) -> Self {
+        Self {
+            name,
+            direction: PortDirection::Input,
+        }
+    }
+
+    pub fn output(name: &'static str) -> Self {
+        Self {
+            name,
+            direction: PortDirection::Output,
+        }
+    }
+
+    pub fn inout(name: &'static str) -> Self {
+        Self {
+            name,
+            direction: PortDirection::InOut,
+        }
+    }
+}
+
+/// View of the blackboard handed to a [`PortAction`]. Ports are resolved to
+/// blackboard keys through the remapping of the node instance, unmapped ports
+/// use a key equal to their own name.
+///
+/// Accessing a port the action didn't declare, reading an output or writing
+/// an input panics.
+pub struct Ports<'a> {
+    blackboard: &'a mut Blackboard,
+    declared: &'a [Port],
+    remap: &'a HashMap<String, String>,
+}
+
+impl<'a> Ports<'a> {
+    pub fn key<'p>(&'p self, port: &'p str) -> &'p str {
+        self.remap.get(port).map(|key| key.as_str()).unwrap_or(port)
+    }
+
+    fn direction(&self, port: &str) -> PortDirection {
+        match self.declared.iter().find(|declared| declared.name == port) {
+            Some(declared) => declared.direction,
+            None => panic!("port {} isn't declared by the action", port),
+        }
+    }
+
+    pub fn get<V: 'static>(&self, port: &str) -> Option<&V> {
+        assert!(
+            self.direction(port).can_read(),
+            "port {} is an output and can't be read",
+            port
+        );
+        self.blackboard.get(self.key(port))
+    }
+
+    pub fn set<V: 'static>(&mut self, port: &str, value: V) {
+        assert!(
+            self.direction(port).can_write(),
+            "port {} is an input and can't be written",
+            port
+        );
+        let key = self.key(port).to_owned();
+        self.blackboard.set(&key, value);
+    }
+}
+
+/// Action that only talks to the blackboard through the ports it declares,
+/// which makes it reusable between trees with differently named keys.
+pub trait PortAction {
+    fn ports(&self) -> Vec<Port>;
+    fn tick(&mut self, ports: &mut Ports) -> Status;
+
+    fn reset(&mut self) {}
+}
+
+struct PortNode<A> {
+    action: A,
+    ports: Vec<Port>,
+    remap: HashMap<String, String>,
+}
+
+impl<T: AsBlackboard, A: PortAction> StatefulAction<T> for PortNode<A> {
+    fn tick(&mut self, data: &mut T) -> Status {
+        let mut ports = Ports {
+            blackboard: data.blackboard_mut(),
+            declared: &self.ports,
+            remap: &self.remap,
+        };
+
+        self.action.tick(&mut ports)
+    }
+
+    fn reset(&mut self) {
+        self.action.reset();
+    }
+}
+
+impl<T: AsBlackboard> Node<T> {
+    /// Creates a node from a [`PortAction`], with `remap` being a list of
+    /// `(port, blackboard key)` pairs for this instance.
+    ///
+    /// Panics if `remap` refers to a port the action doesn't declare.
+    pub fn port_action<A: PortAction + 'static>(
+        name: &str,
+        action: A,
+        remap: &[(&str, &str)],
+    ) -> Node<T> {
+        let ports = action.ports();
+
+        for (port, _) in remap {
+            assert!(
+                ports.iter().any(|declared| declared.name == *port),
+                "Action {} has no port named {}",
+                name,
+                port
+            );
+        }
+
+        let remap = remap
+            .iter()
+            .map(|(port, key)| (port.to_string(), key.to_string()))
+            .collect();
+
+        Self::stateful_action(
+            name,
+            Box::new(PortNode {
+                action,
+                ports,
+                remap,
+            }),
+        )
+    }
+
+    /// Decorator which only ticks `child` while `cond` holds, similar to
+    /// [`Node::named_while_single`], except the condition is only
+    /// re-evaluated once one of the blackboard `keys` changed. `aborts`
+    /// controls whether such a change can interrupt the running child or
+    /// lower priority siblings in a select.
+    pub fn observe(
+        name: &str,
+        keys: &[&str],
+        aborts: Aborts,
+        cond: Box<dyn Fn(&T) -> bool>,
+        child: Node<T>,
+    ) -> Node<T> {
+        let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+
+        Self::new_named(
+            name.to_owned(),
+            Behavior::Observe {
+                cond: cond.into(),
+                revision: Rc::new(move |data: &T| {
+                    keys.iter()
+                        .map(|key| data.blackboard().key_revision(key))
+                        .max()
+                        .unwrap_or(0)
+                }),
+                aborts,
+                seen: None,
+                child: Rc::new(RefCell::new(child)),
+            },
+        )
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{path::NodeId, prelude::*};
+use std::cell::RefCell;
+
+/// Where [`BehaviorTree::debug_tick`] stops in the middle of a tick.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Breakpoint {
+    /// Pauses before the node is ticked.
+    Id(NodeId),
+    /// Pauses before any node with this name is ticked.
+    Name(String),
+    /// Pauses after a tick changed the status of a node to `to`, `id: None`
+    /// matching any node.
+    Status { id: Option<NodeId>, to: Status },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PauseReason {
+    Breakpoint(Breakpoint),
+    /// Step mode is on, see [`BehaviorTree::set_step_mode`].
+    Step,
+}
+
+/// Where a [`BehaviorTree::debug_tick`] paused.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pause {
+    /// Node about to be ticked, or the one that just changed its status for
+    /// [`Breakpoint::Status`].
+    pub id: NodeId,
+    pub path: String,
+    pub reason: PauseReason,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugTick {
+    Finished(Status),
+    Paused(Pause),
+}
+
+/// Breakpoints of a tree along with the state needed to resume a paused tick.
+#[derive(Default)]
+pub(crate) struct Debugger {
+    breakpoints: Vec<Breakpoint>,
+    step_mode: bool,
+    /// Nodes being ticked right now, from the root down.
+    stack: Vec<NodeId>,
+    /// Set once a node was ticked in step mode, the next one pauses.
+    stepped: bool,
+    paused: Option<(NodeId, PauseReason)>,
+    /// Ancestors of the node the previous tick paused at, which are ticked
+    /// again on the way back to it without stopping.
+    resume_path: Vec<NodeId>,
+    /// Node the previous tick paused in front of, its breakpoints are skipped.
+    resume_node: Option<NodeId>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Debugger>> = const { RefCell::new(None) };
+}
+
+impl Debugger {
+    /// Whether the node can be ticked, otherwise it stays pending.
+    fn enter<T>(&mut self, node: &Node<T>) -> bool {
+        if self.paused.is_some() {
+            return false;
+        }
+
+        let depth = self.stack.len();
+        let on_resume_path = self.resume_path.starts_with(&self.stack);
+
+        if on_resume_path && self.resume_path.get(depth) == Some(&node.id) {
+            self.stack.push(node.id);
+            return true;
+        }
+
+        let resuming =
+            on_resume_path && depth == self.resume_path.len() && self.resume_node == Some(node.id);
+
+        if !resuming {
+            let hit = self.breakpoints.iter().find(|breakpoint| match breakpoint {
+                Breakpoint::Id(id) => *id == node.id,
+                Breakpoint::Name(name) => node.name.as_ref() == Some(name),
+                Breakpoint::Status { .. } => false,
+            });
+
+            if let Some(breakpoint) = hit {
+                self.pause(node.id, PauseReason::Breakpoint(breakpoint.clone()));
+                return false;
+            }
+        }
+
+        if self.step_mode {
+            if self.stepped {
+                self.pause(node.id, PauseReason::Step);
+                return false;
+            }
+
+            self.stepped = true;
+        }
+
+        self.stack.push(node.id);
+        true
+    }
+
+    fn leave<T>(&mut self, node: &Node<T>, previous: Status) {
+        self.stack.pop();
+
+        if self.paused.is_some() || previous == node.status {
+            return;
+        }
+
+        let hit = self.breakpoints.iter().find(|breakpoint| match breakpoint {
+            Breakpoint::Status { id, to } => {
+                *to == node.status && (id.is_none() || *id == Some(node.id))
+            }
+            _ => false,
+        });
+
+        if let Some(breakpoint) = hit {
+            let reason = PauseReason::Breakpoint(breakpoint.clone());
+            self.paused = Some((node.id, reason));
+            self.resume_path = self.stack.clone();
+            self.resume_node = None;
+        }
+    }
+
+    fn pause(&mut self, id: NodeId, reason: PauseReason) {
+        self.paused = Some((id, reason));
+        self.resume_path = self.stack.clone();
+        self.resume_node = Some(id);
+    }
+}
+
+/// Called by [`Node::tick`], returns `false` when the node has to stay
+/// pending because the tick is paused.
+pub(crate) fn enter<T>(node: &Node<T>) -> bool {
+    ACTIVE.with(|active| match active.borrow_mut().as_mut() {
+        Some(debugger) => debugger.enter(node),
+        None => true,
+    })
+}
+
+pub(crate) fn leave<T>(node: &Node<T>, previous: Status) {
+    ACTIVE.with(|active| {
+        if let Some(debugger) = active.borrow_mut().as_mut() {
+            debugger.leave(node, previous);
+        }
+    })
+}
+
+/// Installs the debugger for the duration of a tick, putting back the one
+/// it replaced afterwards, even if the tick panics.
+struct Installed<'a> {
+    debugger: &'a mut Debugger,
+    previous: Option<Debugger>,
+}
+
+impl<'a> Installed<'a> {
+    fn new(debugger: &'a mut Debugger) -> Self {
+        let installed = std::mem::take(debugger);
+        let previous = ACTIVE.with(|active| active.borrow_mut().replace(installed));
+
+        Self { debugger, previous }
+    }
+}
+
+impl Drop for Installed<'_> {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+
+        if let Some(debugger) = ACTIVE.with(|active| active.replace(previous)) {
+            *self.debugger = debugger;
+        }
+    }
+}
+
+/// Hides the debugger of the tree being debugged from a tree ticked in the
+/// middle of it, until dropped.
+pub(crate) struct Suspended(Option<Debugger>);
+
+pub(crate) fn suspend() -> Suspended {
+    Suspended(ACTIVE.with(|active| active.borrow_mut().take()))
+}
+
+impl Drop for Suspended {
+    fn drop(&mut self) {
+        if let Some(debugger) = self.0.take() {
+            ACTIVE.with(|active| *active.borrow_mut() = Some(debugger));
+        }
+    }
+}
+
+impl<T> BehaviorTree<T> {
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.debugger.breakpoints.push(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) {
+        self.debugger.breakpoints.retain(|b| b != breakpoint);
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.debugger.breakpoints.clear();
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.debugger.breakpoints
+    }
+
+    /// In step mode every [`BehaviorTree::debug_tick`] ticks a single node
+    /// and pauses in front of the next one.
+    pub fn set_step_mode(&mut self, step_mode: bool) {
+        self.debugger.step_mode = step_mode;
+    }
+
+    /// Like `tick`, but stops at breakpoints. Nodes which weren't reached
+    /// when the tick paused are left pending, so their parents see them as
+    /// running, and the next `debug_tick` picks up from there.
+    ///
+    /// Nodes on the way back to the paused one are ticked again, so
+    /// conditions above it are re-evaluated and may take the tree elsewhere.
+    pub fn debug_tick(&mut self, delta: f64, context: &mut T) -> DebugTick {
+        self.debugger.stack.clear();
+        self.debugger.stepped = false;
+        self.debugger.paused = None;
+
+        let status = {
+            let _installed = Installed::new(&mut self.debugger);
+
+            let delta = self.elapsed + delta;
+            self.elapsed = 0.0;
+            self.notified = false;
+
+            self.tree.borrow_mut().tick(delta, context)
+        };
+
+        match self.debugger.paused.clone() {
+            Some((id, reason)) => DebugTick::Paused(Pause {
+                id,
+                path: self.path_of(id).unwrap_or_default(),
+                reason,
+            }),
+            None => {
+                self.debugger.resume_path.clear();
+                self.debugger.resume_node = None;
+                DebugTick::Finished(status)
+            }
+        }
+    }
+}
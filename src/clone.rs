@@ -0,0 +1,126 @@
+use crate::{
+    path::{join, NodeId},
+    prelude::*,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Error returned by [`Node::clone_tree`] when the tree contains a stateful
+/// action without a [`StatefulAction::box_clone`] implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloneError {
+    pub path: String,
+}
+
+impl std::fmt::Display for CloneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stateful action at {} can't be cloned", self.path)
+    }
+}
+
+impl std::error::Error for CloneError {}
+
+fn clone_child<T>(
+    child: &Rc<RefCell<Node<T>>>,
+    path: String,
+) -> Result<Rc<RefCell<Node<T>>>, CloneError> {
+    Ok(Rc::new(RefCell::new(child.borrow().clone_at(&path)?)))
+}
+
+impl<T> Node<T> {
+    /// Deep copy of the subtree with fresh `Rc`s and ids, in its initial
+    /// state, so that a prototype tree can be built once and then copied for
+    /// every agent. Children shared between several parents are copied once
+    /// for each of them.
+    pub fn clone_tree(&self) -> Result<Node<T>, CloneError> {
+        self.clone_at("root")
+    }
+
+    fn clone_at(&self, path: &str) -> Result<Node<T>, CloneError> {
+        let mut segments = self
+            .child_segments()
+            .into_iter()
+            .map(|segment| join(path, &segment));
+        let mut next_path = || segments.next().unwrap_or_default();
+
+        let behavior = match &self.behavior {
+            Behavior::Wait { max, .. } => Behavior::Wait {
+                curr: *max,
+                max: *max,
+            },
+            Behavior::RandomWait { max, .. } => {
+                let mut behavior = Behavior::RandomWait {
+                    curr: 0.0,
+                    curr_max: 0.0,
+                    max: *max,
+                };
+                // Rolls a new duration for the copy.
+                behavior.reset();
+                behavior
+            }
+            Behavior::Cond(name, cond, positive, negative) => Behavior::Cond(
+                name.clone(),
+                cond.clone(),
+                clone_child(positive, next_path())?,
+                clone_child(negative, next_path())?,
+            ),
+            Behavior::Sequence(_, seq) => Behavior::Sequence(
+                0,
+                seq.iter()
+                    .map(|child| clone_child(child, next_path()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Behavior::Select(_, seq) => Behavior::Select(
+                0,
+                seq.iter()
+                    .map(|child| clone_child(child, next_path()))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Behavior::Action(name, action) => Behavior::Action(name.clone(), *action),
+            Behavior::ActionSuccess(name, action) => Behavior::ActionSuccess(name.clone(), *action),
+            Behavior::FallibleAction(name, action, _) => {
+                Behavior::FallibleAction(name.clone(), *action, None)
+            }
+            Behavior::StatefulAction(name, action) => {
+                let mut action = action.box_clone().ok_or_else(|| CloneError {
+                    path: path.to_owned(),
+                })?;
+                action.reset();
+
+                Behavior::StatefulAction(name.clone(), action)
+            }
+            Behavior::While(cond, child) => {
+                Behavior::While(cond.clone(), clone_child(child, next_path())?)
+            }
+            Behavior::Observe {
+                cond,
+                revision,
+                aborts,
+                child,
+                ..
+            } => Behavior::Observe {
+                cond: cond.clone(),
+                revision: revision.clone(),
+                aborts: *aborts,
+                seen: None,
+                child: clone_child(child, next_path())?,
+            },
+        };
+
+        Ok(Node {
+            id: NodeId::next(),
+            name: self.name.clone(),
+            behavior,
+            status: Status::Initialized,
+            collapse_as: self.collapse_as.clone(),
+            forced: None,
+        })
+    }
+}
+
+impl<T> BehaviorTree<T> {
+    /// Independent copy of the tree in its initial state, see
+    /// [`Node::clone_tree`].
+    pub fn clone_tree(&self) -> Result<BehaviorTree<T>, CloneError> {
+        Ok(BehaviorTree::new(self.tree.borrow().clone_tree()?))
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{path::NodeId, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+/// Command sent by a client to a [`DebugServer`], one JSON object per line,
+/// e.g. `{"cmd":"force","path":"root/Attack","status":"Failure"}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Command {
+    /// Asks for a [`Message::Tree`] with the current structure.
+    GetTree,
+    Pause,
+    Resume,
+    /// Ticks the tree once and pauses it.
+    Step,
+    /// Pauses the tree after a tick in which the node changed its status, to
+    /// `status` if given.
+    Breakpoint {
+        path: String,
+        #[serde(default)]
+        status: Option<Status>,
+    },
+    ClearBreakpoint {
+        path: String,
+    },
+    /// Forces the node to report `status` instead of being ticked, `None`
+    /// ticks it normally again. See [`BehaviorTree::force`].
+    Force {
+        path: String,
+        #[serde(default)]
+        status: Option<Status>,
+    },
+    /// Leaves the node out until it is forced again, see [`Override::Skip`].
+    Skip {
+        path: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeInfo {
+    pub id: NodeId,
+    pub path: String,
+    pub parent: Option<NodeId>,
+    pub kind: String,
+    pub name: String,
+    pub status: Status,
+    pub forced: Option<Override>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub id: NodeId,
+    pub status: Status,
+}
+
+/// Message sent by a [`DebugServer`] to its clients, one JSON object per line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Structure of the tree, sent when a client connects and on request.
+    Tree {
+        tick: u64,
+        paused: bool,
+        nodes: Vec<NodeInfo>,
+    },
+    /// Sent after every tick with the nodes whose status changed.
+    Tick {
+        tick: u64,
+        status: Status,
+        changes: Vec<StatusChange>,
+    },
+    Paused {
+        tick: u64,
+        /// Path of the breakpoint which paused the tree, if any.
+        breakpoint: Option<String>,
+    },
+    Resumed {
+        tick: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+trait Stream: Read + Write {}
+
+impl<S: Read + Write> Stream for S {}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener),
+}
+
+impl Listener {
+    fn accept(&self) -> io::Result<Box<dyn Stream>> {
+        match self {
+            Listener::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nonblocking(true)?;
+                stream.set_nodelay(true)?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nonblocking(true)?;
+                Ok(Box::new(stream))
+            }
+        }
+    }
+}
+
+struct Client {
+    stream: Box<dyn Stream>,
+    /// Received bytes not yet terminated by a newline.
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    connected: bool,
+}
+
+impl Client {
+    fn send(&mut self, message: &Message) {
+        // Serializing these types can't fail.
+        serde_json::to_writer(&mut self.outgoing, message).unwrap();
+        self.outgoing.push(b'\n');
+    }
+
+    fn receive(&mut self) -> Vec<Vec<u8>> {
+        let mut buffer = [0; 4096];
+
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => {
+                    self.connected = false;
+                    break;
+                }
+                Ok(n) => self.incoming.extend_from_slice(&buffer[..n]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.connected = false;
+                    break;
+                }
+            }
+        }
+
+        let mut lines = vec![];
+        while let Some(end) = self.incoming.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn flush(&mut self) {
+        while !self.outgoing.is_empty() && self.connected {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => self.connected = false,
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => self.connected = false,
+            }
+        }
+    }
+}
+
+/// Lets external tools inspect and control a tree in a running process. The
+/// server never blocks: call [`DebugServer::tick`] in place of
+/// `BehaviorTree::tick`, which handles pending connections and commands
+/// before ticking the tree, unless it is paused.
+///
+/// See [`DebugClient`] for the client side of the protocol.
+pub struct DebugServer {
+    listener: Listener,
+    clients: Vec<Client>,
+    paused: bool,
+    step_requested: bool,
+    breakpoints: Vec<(String, Option<Status>)>,
+    ticks: u64,
+    statuses: HashMap<NodeId, Status>,
+}
+
+impl DebugServer {
+    fn new(listener: Listener) -> Self {
+        Self {
+            listener,
+            clients: vec![],
+            paused: false,
+            step_requested: false,
+            breakpoints: vec![],
+            ticks: 0,
+            statuses: HashMap::new(),
+        }
+    }
+
+    /// Listens on a TCP address, e.g. `127.0.0.1:7777` (port `0` picks a
+    /// free one, see [`DebugServer::local_addr`]).
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Listener::Tcp(listener)))
+    }
+
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Listener::Unix(listener)))
+    }
+
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Accepts new clients and handles their commands without ticking the
+    /// tree. Returns the number of commands handled.
+    pub fn poll<T>(&mut self, tree: &mut BehaviorTree<T>) -> usize {
+        while let Ok(stream) = self.listener.accept() {
+            let mut client = Client {
+                stream,
+                incoming: vec![],
+                outgoing: vec![],
+                connected: true,
+            };
+            client.send(&self.tree_message(tree));
+            self.clients.push(client);
+        }
+
+        let mut handled = 0;
+
+        for i in 0..self.clients.len() {
+            for line in self.clients[i].receive() {
+                let reply = match serde_json::from_slice::<Command>(&line) {
+                    Ok(command) => self.handle(command, tree),
+                    Err(err) => Some(Message::Error {
+                        message: format!("invalid command: {}", err),
+                    }),
+                };
+
+                if let Some(reply) = reply {
+                    // Only the tree is a reply to the sender, everything else
+                    // concerns all clients.
+                    match reply {
+                        Message::Tree { .. } | Message::Error { .. } => {
+                            self.clients[i].send(&reply)
+                        }
+                        _ => self.broadcast(&reply),
+                    }
+                }
+
+                handled += 1;
+            }
+        }
+
+        self.flush();
+        handled
+    }
+
+    fn handle<T>(&mut self, command: Command, tree: &mut BehaviorTree<T>) -> Option<Message> {
+        let unknown = |path: &str| Message::Error {
+            message: format!("no node at {}", path),
+        };
+
+        match command {
+            Command::GetTree => Some(self.tree_message(tree)),
+            Command::Pause => {
+                self.paused = true;
+                Some(Message::Paused {
+                    tick: self.ticks,
+                    breakpoint: None,
+                })
+            }
+            Command::Resume => {
+                self.paused = false;
+                Some(Message::Resumed { tick: self.ticks })
+            }
+            Command::Step => {
+                self.paused = true;
+                self.step_requested = true;
+                None
+            }
+            Command::Breakpoint { path, status } => {
+                if tree.find_by_path(&path).is_none() {
+                    return Some(unknown(&path));
+                }
+
+                self.breakpoints.retain(|(p, _)| *p != path);
+                self.breakpoints.push((path, status));
+                None
+            }
+            Command::ClearBreakpoint { path } => {
+                self.breakpoints.retain(|(p, _)| *p != path);
+                None
+            }
+            Command::Force { path, status } => {
+                if tree.force_path(&path, status.map(Override::Status)) {
+                    None
+                } else {
+                    Some(unknown(&path))
+                }
+            }
+            Command::Skip { path } => {
+                if tree.force_path(&path, Some(Override::Skip)) {
+                    None
+                } else {
+                    Some(unknown(&path))
+                }
+            }
+        }
+    }
+
+    /// Ticks the tree unless it is paused, after handling commands like
+    /// [`DebugServer::poll`]. Returns `None` when the tick was skipped.
+    pub fn tick<T>(
+        &mut self,
+        tree: &mut BehaviorTree<T>,
+        delta: f64,
+        context: &mut T,
+    ) -> Option<Status> {
+        self.poll(tree);
+
+        if self.paused && !self.step_requested {
+            return None;
+        }
+
+        self.step_requested = false;
+        self.ticks += 1;
+        let status = tree.tick(delta, context);
+
+        let mut changes = vec![];
+        let mut hit = None;
+
+        for (path, node) in tree.nodes() {
+            let node = node.borrow();
+
+            if self.statuses.insert(node.id, node.status) == Some(node.status) {
+                continue;
+            }
+
+            changes.push(StatusChange {
+                id: node.id,
+                status: node.status,
+            });
+
+            let breakpoint = self
+                .breakpoints
+                .iter()
+                .any(|(p, s)| *p == path && (s.is_none() || *s == Some(node.status)));
+
+            if breakpoint && hit.is_none() {
+                hit = Some(path);
+            }
+        }
+
+        self.broadcast(&Message::Tick {
+            tick: self.ticks,
+            status,
+            changes,
+        });
+
+        if let Some(path) = hit {
+            self.paused = true;
+            self.broadcast(&Message::Paused {
+                tick: self.ticks,
+                breakpoint: Some(path),
+            });
+        }
+
+        self.flush();
+        Some(status)
+    }
+
+    fn tree_message<T>(&self, tree: &BehaviorTree<T>) -> Message {
+        let mut ids: HashMap<String, NodeId> = HashMap::new();
+
+        let nodes = tree
+            .nodes()
+            .into_iter()
+            .map(|(path, node)| {
+                let node = node.borrow();
+                let parent = path.rfind('/').and_then(|i| ids.get(&path[..i]).copied());
+                ids.insert(path.clone(), node.id);
+
+                NodeInfo {
+                    id: node.id,
+                    path,
+                    parent,
+                    kind: node.behavior.kind().to_owned(),
+                    name: node.name(),
+                    status: node.status,
+                    forced: node.forced,
+                }
+            })
+            .collect();
+
+        Message::Tree {
+            tick: self.ticks,
+            paused: self.paused,
+            nodes,
+        }
+    }
+
+    fn broadcast(&mut self, message: &Message) {
+        for client in &mut self.clients {
+            client.send(message);
+        }
+    }
+
+    fn flush(&mut self) {
+        for client in &mut self.clients {
+            client.flush();
+        }
+
+        self.clients.retain(|client| client.connected);
+    }
+}
+
+/// Blocking client for a [`DebugServer`], used by the `debug_client`
+/// example and handy for writing tools.
+pub struct DebugClient {
+    reader: BufReader<Box<dyn Read + Send>>,
+    writer: Box<dyn Write + Send>,
+}
+
+impl DebugClient {
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+
+        Ok(Self {
+            reader: BufReader::new(Box::new(stream.try_clone()?)),
+            writer: Box::new(stream),
+        })
+    }
+
+    #[cfg(unix)]
+    pub fn connect_unix(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+
+        Ok(Self {
+            reader: BufReader::new(Box::new(stream.try_clone()?)),
+            writer: Box::new(stream),
+        })
+    }
+
+    pub fn send(&mut self, command: &Command) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, command)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+
+    /// Waits for the next message from the server.
+    pub fn recv(&mut self) -> io::Result<Message> {
+        let mut line = String::new();
+
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(serde_json::from_str(&line)?)
+    }
+}
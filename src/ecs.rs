@@ -0,0 +1,175 @@
+use crate::prelude::*;
+use std::{marker::PhantomData, ptr::NonNull};
+
+/// Minimal component access needed by [`EcsContext`], implemented on top of
+/// the world type of an ECS library. For example with `hecs`:
+///
+/// ```ignore
+/// impl EcsWorld for hecs::World {
+///     type Entity = hecs::Entity;
+///
+///     fn contains(&self, entity: Self::Entity) -> bool {
+///         hecs::World::contains(self, entity)
+///     }
+///
+///     fn with<C: Send + Sync + 'static, R>(&self, entity: Self::Entity, f: impl FnOnce(&C) -> R) -> Option<R> {
+///         self.get::<&C>(entity).ok().map(|c| f(&c))
+///     }
+///
+///     fn with_mut<C: Send + Sync + 'static, R>(&mut self, entity: Self::Entity, f: impl FnOnce(&mut C) -> R) -> Option<R> {
+///         self.get::<&mut C>(entity).ok().map(|mut c| f(&mut c))
+///     }
+///
+///     fn insert<C: Send + Sync + 'static>(&mut self, entity: Self::Entity, component: C) -> bool {
+///         self.insert_one(entity, component).is_ok()
+///     }
+///
+///     fn remove<C: Send + Sync + 'static>(&mut self, entity: Self::Entity) -> Option<C> {
+///         self.remove_one::<C>(entity).ok()
+///     }
+/// }
+/// ```
+pub trait EcsWorld {
+    type Entity: Copy + Eq + std::fmt::Debug;
+
+    fn contains(&self, entity: Self::Entity) -> bool;
+
+    fn with<C: Send + Sync + 'static, R>(
+        &self,
+        entity: Self::Entity,
+        f: impl FnOnce(&C) -> R,
+    ) -> Option<R>;
+
+    fn with_mut<C: Send + Sync + 'static, R>(
+        &mut self,
+        entity: Self::Entity,
+        f: impl FnOnce(&mut C) -> R,
+    ) -> Option<R>;
+
+    /// Adds or replaces a component, returns `false` when the entity doesn't
+    /// exist.
+    fn insert<C: Send + Sync + 'static>(&mut self, entity: Self::Entity, component: C) -> bool;
+
+    fn remove<C: Send + Sync + 'static>(&mut self, entity: Self::Entity) -> Option<C>;
+}
+
+/// Context of a tree ticked with [`BehaviorTree::tick_entity`], giving
+/// actions access to the world and the entity the tree belongs to.
+///
+/// It's a view over the world borrowed by `tick_entity`, which keeps the
+/// tree's type free of that borrow's lifetime. Contexts can't be built
+/// outside of `tick_entity` and actions only ever get `&mut EcsContext`, so
+/// the view can't outlive the tick.
+pub struct EcsContext<W: EcsWorld> {
+    world: NonNull<W>,
+    pub entity: W::Entity,
+    /// Invariant in `W` like the `&mut W` it stands for.
+    _world: PhantomData<*mut W>,
+}
+
+impl<W: EcsWorld> EcsContext<W> {
+    pub fn world(&self) -> &W {
+        // SAFETY: the pointer comes from the `&mut W` held by `tick_entity`
+        // for as long as the context exists, see above.
+        unsafe { self.world.as_ref() }
+    }
+
+    pub fn world_mut(&mut self) -> &mut W {
+        // SAFETY: as in `world`, and `&mut self` makes the access unique.
+        unsafe { self.world.as_mut() }
+    }
+
+    pub fn has<C: Send + Sync + 'static>(&self) -> bool {
+        self.world().with(self.entity, |_: &C| ()).is_some()
+    }
+
+    pub fn get<C: Clone + Send + Sync + 'static>(&self) -> Option<C> {
+        self.world().with(self.entity, C::clone)
+    }
+
+    pub fn with<C: Send + Sync + 'static, R>(&self, f: impl FnOnce(&C) -> R) -> Option<R> {
+        self.world().with(self.entity, f)
+    }
+
+    pub fn with_mut<C: Send + Sync + 'static, R>(
+        &mut self,
+        f: impl FnOnce(&mut C) -> R,
+    ) -> Option<R> {
+        let entity = self.entity;
+        self.world_mut().with_mut(entity, f)
+    }
+
+    pub fn insert<C: Send + Sync + 'static>(&mut self, component: C) -> bool {
+        let entity = self.entity;
+        self.world_mut().insert(entity, component)
+    }
+
+    pub fn remove<C: Send + Sync + 'static>(&mut self) -> Option<C> {
+        let entity = self.entity;
+        self.world_mut().remove(entity)
+    }
+}
+
+/// Condition for `Node::cond` which checks that the entity has a component,
+/// e.g. `Node::cond("armed", has_component::<Weapon, _>, attack, flee)`.
+pub fn has_component<C: Send + Sync + 'static, W: EcsWorld>(context: &EcsContext<W>) -> bool {
+    context.has::<C>()
+}
+
+impl<W: EcsWorld> BehaviorTree<EcsContext<W>> {
+    /// Ticks the tree of `entity`, with the context borrowing `world` for the
+    /// duration of the tick.
+    pub fn tick_entity(&mut self, delta: f64, world: &mut W, entity: W::Entity) -> Status {
+        let mut context = EcsContext {
+            world: NonNull::from(world),
+            entity,
+            _world: PhantomData,
+        };
+
+        self.tick(delta, &mut context)
+    }
+}
+
+struct ComponentAction<C> {
+    action: Box<dyn FnMut(&mut C) -> Status>,
+}
+
+impl<W: EcsWorld, C: Send + Sync + 'static> StatefulAction<EcsContext<W>> for ComponentAction<C> {
+    fn tick(&mut self, data: &mut EcsContext<W>) -> Status {
+        let action = &mut self.action;
+        data.with_mut(|component: &mut C| action(component))
+            .unwrap_or(Status::Failure)
+    }
+
+    fn reset(&mut self) {}
+}
+
+impl<W: EcsWorld + 'static> Node<EcsContext<W>> {
+    /// Action operating on a component of the entity, which fails when the
+    /// entity doesn't have it.
+    pub fn component_action<C: Send + Sync + 'static>(
+        name: &str,
+        action: impl FnMut(&mut C) -> Status + 'static,
+    ) -> Self {
+        Self::stateful_action(
+            name,
+            Box::new(ComponentAction {
+                action: Box::new(action),
+            }),
+        )
+    }
+
+    /// Runs the child while a component of the entity satisfies `cond`. An
+    /// entity without the component counts as not satisfying it.
+    pub fn component_while<C: Send + Sync + 'static>(
+        name: &str,
+        cond: impl Fn(&C) -> bool + 'static,
+        child: Node<EcsContext<W>>,
+    ) -> Self {
+        Self::named_while_single(
+            name,
+            Box::new(move |data: &EcsContext<W>| data.with(|c: &C| cond(c)).unwrap_or(false)),
+            child,
+        )
+    }
+}
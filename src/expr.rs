@@ -0,0 +1,612 @@
+use crate::prelude::*;
+use crate::{AsBlackboard, Blackboard};
+use std::rc::Rc;
+
+/// Value of an [`Expression`] or one of its parts.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "number",
+            Value::Bool(_) => "bool",
+            Value::Str(_) => "string",
+        }
+    }
+
+    /// Reads a blackboard entry of a supported type (any primitive number,
+    /// `bool`, `String` or `&'static str`).
+    fn from_blackboard(blackboard: &Blackboard, key: &str) -> Option<Value> {
+        macro_rules! numbers {
+            ($($ty:ty),*) => {
+                $(
+                    if let Some(value) = blackboard.get::<$ty>(key) {
+                        return Some(Value::Number(*value as f64));
+                    }
+                )*
+            };
+        }
+
+        numbers!(f64, f32, i64, i32, i16, i8, u64, u32, u16, u8, isize, usize);
+
+        if let Some(value) = blackboard.get::<bool>(key) {
+            Some(Value::Bool(*value))
+        } else if let Some(value) = blackboard.get::<String>(key) {
+            Some(Value::Str(value.clone()))
+        } else {
+            blackboard
+                .get::<&'static str>(key)
+                .map(|value| Value::Str((*value).to_owned()))
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Number(value) => write!(f, "{}", value),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Str(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+/// Error found while parsing or evaluating an [`Expression`], pointing at
+/// the offending part of the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExprError {
+    pub message: String,
+    /// Byte offset into `source`.
+    pub position: usize,
+    pub source: String,
+}
+
+impl std::fmt::Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let column = self.source[..self.position].chars().count();
+
+        writeln!(f, "{} at column {}", self.message, column + 1)?;
+        writeln!(f, "  {}", self.source)?;
+        write!(f, "  {}^", " ".repeat(column))
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+const OPERATORS: &[&str] = &[
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%",
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ast {
+    Literal(Value),
+    Key(String, usize),
+    Not(Box<Ast>, usize),
+    Neg(Box<Ast>, usize),
+    Binary(BinaryOp, Box<Ast>, Box<Ast>, usize),
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: String, position: usize) -> ExprError {
+        ExprError {
+            message,
+            position,
+            source: self.source.to_owned(),
+        }
+    }
+
+    fn tokenize(&mut self) -> Result<(), ExprError> {
+        let source = self.source;
+        let mut chars = source.char_indices().peekable();
+
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_ascii_digit() {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                let number = source[start..end].parse().map_err(|_| {
+                    self.error(format!("invalid number `{}`", &source[start..end]), start)
+                })?;
+                self.tokens.push((Token::Number(number), start));
+            } else if c.is_alphabetic() || c == '_' {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+
+                self.tokens
+                    .push((Token::Ident(source[start..end].to_owned()), start));
+            } else if c == '"' || c == '\'' {
+                chars.next();
+                let mut value = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some((_, end)) if end == c => break,
+                        Some((_, c)) => value.push(c),
+                        None => return Err(self.error("unterminated string".to_owned(), start)),
+                    }
+                }
+
+                self.tokens.push((Token::Str(value), start));
+            } else if c == '(' || c == ')' {
+                chars.next();
+                let token = if c == '(' {
+                    Token::LParen
+                } else {
+                    Token::RParen
+                };
+                self.tokens.push((token, start));
+            } else {
+                let op = OPERATORS
+                    .iter()
+                    .find(|op| source[start..].starts_with(*op))
+                    .ok_or_else(|| self.error(format!("unexpected character `{}`", c), start))?;
+
+                for _ in 0..op.len() {
+                    chars.next();
+                }
+                self.tokens.push((Token::Op(op), start));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    /// Position of the next token, or the end of the source.
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map_or(self.source.len(), |(_, position)| *position)
+    }
+
+    fn unexpected(&self, expected: &str) -> ExprError {
+        let found = match self.peek() {
+            Some(Token::Number(number)) => format!("`{}`", number),
+            Some(Token::Str(value)) => format!("{:?}", value),
+            Some(Token::Ident(ident)) => format!("`{}`", ident),
+            Some(Token::Op(op)) => format!("`{}`", op),
+            Some(Token::LParen) => "`(`".to_owned(),
+            Some(Token::RParen) => "`)`".to_owned(),
+            None => "end of expression".to_owned(),
+        };
+
+        self.error(
+            format!("expected {}, found {}", expected, found),
+            self.position(),
+        )
+    }
+
+    /// Parses binary operators of the given precedence level and above.
+    fn binary(&mut self, level: usize) -> Result<Ast, ExprError> {
+        const LEVELS: &[&[(&str, BinaryOp)]] = &[
+            &[("||", BinaryOp::Or)],
+            &[("&&", BinaryOp::And)],
+            &[("==", BinaryOp::Eq), ("!=", BinaryOp::Ne)],
+            &[
+                ("<", BinaryOp::Lt),
+                ("<=", BinaryOp::Le),
+                (">", BinaryOp::Gt),
+                (">=", BinaryOp::Ge),
+            ],
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Rem),
+            ],
+        ];
+
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op(token)) => LEVELS[level]
+                    .iter()
+                    .find(|(op, _)| op == token)
+                    .map(|(_, op)| *op),
+                _ => None,
+            };
+
+            match op {
+                Some(op) => {
+                    let position = self.position();
+                    self.next += 1;
+                    let rhs = self.binary(level + 1)?;
+                    lhs = Ast::Binary(op, Box::new(lhs), Box::new(rhs), position);
+                }
+                None => return Ok(lhs),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Ast, ExprError> {
+        let position = self.position();
+
+        match self.peek() {
+            Some(Token::Op("!")) => {
+                self.next += 1;
+                Ok(Ast::Not(Box::new(self.unary()?), position))
+            }
+            Some(Token::Op("-")) => {
+                self.next += 1;
+                Ok(Ast::Neg(Box::new(self.unary()?), position))
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Ast, ExprError> {
+        let position = self.position();
+
+        let ast = match self.peek().cloned() {
+            Some(Token::Number(number)) => Ast::Literal(Value::Number(number)),
+            Some(Token::Str(value)) => Ast::Literal(Value::Str(value)),
+            Some(Token::Ident(ident)) if ident == "true" => Ast::Literal(Value::Bool(true)),
+            Some(Token::Ident(ident)) if ident == "false" => Ast::Literal(Value::Bool(false)),
+            Some(Token::Ident(ident)) => Ast::Key(ident, position),
+            Some(Token::LParen) => {
+                self.next += 1;
+                let ast = self.binary(0)?;
+
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(self.unexpected("`)`"));
+                }
+
+                ast
+            }
+            _ => return Err(self.unexpected("a value")),
+        };
+
+        self.next += 1;
+        Ok(ast)
+    }
+}
+
+/// Parsed expression over the keys of a [`Blackboard`], such as
+/// `health < 30 && (ammo > 0 || state == "cornered")`.
+///
+/// Supports numbers, strings in single or double quotes, `true`/`false`,
+/// the `!`, `&&` and `||` boolean operators, comparisons, `+` (also for
+/// strings), `-`, `*`, `/` and `%`. Any other identifier refers to a
+/// blackboard entry, which must hold a primitive number, `bool`, `String` or
+/// `&'static str`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    source: String,
+    ast: Ast,
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ExprError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let mut parser = Parser {
+            source,
+            tokens: vec![],
+            next: 0,
+        };
+
+        parser.tokenize()?;
+        let ast = parser.binary(0)?;
+
+        if parser.peek().is_some() {
+            return Err(parser.unexpected("an operator"));
+        }
+
+        Ok(Self {
+            source: source.to_owned(),
+            ast,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Blackboard keys the expression refers to, in order of appearance.
+    pub fn keys(&self) -> Vec<&str> {
+        fn collect<'a>(ast: &'a Ast, keys: &mut Vec<&'a str>) {
+            match ast {
+                Ast::Literal(_) => {}
+                Ast::Key(key, _) => {
+                    if !keys.contains(&key.as_str()) {
+                        keys.push(key);
+                    }
+                }
+                Ast::Not(ast, _) | Ast::Neg(ast, _) => collect(ast, keys),
+                Ast::Binary(_, lhs, rhs, _) => {
+                    collect(lhs, keys);
+                    collect(rhs, keys);
+                }
+            }
+        }
+
+        let mut keys = vec![];
+        collect(&self.ast, &mut keys);
+        keys
+    }
+
+    fn error(&self, message: String, position: usize) -> ExprError {
+        ExprError {
+            message,
+            position,
+            source: self.source.clone(),
+        }
+    }
+
+    pub fn eval(&self, blackboard: &Blackboard) -> Result<Value, ExprError> {
+        self.eval_ast(&self.ast, blackboard)
+    }
+
+    /// Evaluates an expression that must produce a `bool`.
+    pub fn check(&self, blackboard: &Blackboard) -> Result<bool, ExprError> {
+        match self.eval(blackboard)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(self.error(
+                format!(
+                    "expected the condition to be a bool, found {}",
+                    value.type_name()
+                ),
+                0,
+            )),
+        }
+    }
+
+    fn eval_ast(&self, ast: &Ast, blackboard: &Blackboard) -> Result<Value, ExprError> {
+        match ast {
+            Ast::Literal(value) => Ok(value.clone()),
+            Ast::Key(key, position) => Value::from_blackboard(blackboard, key).ok_or_else(|| {
+                let message = if blackboard.contains_key(key) {
+                    format!("blackboard entry `{}` has an unsupported type", key)
+                } else {
+                    format!("blackboard has no entry `{}`", key)
+                };
+                self.error(message, *position)
+            }),
+            Ast::Not(ast, position) => match self.eval_ast(ast, blackboard)? {
+                Value::Bool(value) => Ok(Value::Bool(!value)),
+                value => Err(self.error(
+                    format!("cannot apply `!` to {}", value.type_name()),
+                    *position,
+                )),
+            },
+            Ast::Neg(ast, position) => match self.eval_ast(ast, blackboard)? {
+                Value::Number(value) => Ok(Value::Number(-value)),
+                value => Err(self.error(
+                    format!("cannot apply `-` to {}", value.type_name()),
+                    *position,
+                )),
+            },
+            Ast::Binary(op @ (BinaryOp::And | BinaryOp::Or), lhs, rhs, position) => {
+                let short_circuit = *op == BinaryOp::Or;
+                let symbol = if short_circuit { "||" } else { "&&" };
+
+                for ast in &[lhs, rhs] {
+                    match self.eval_ast(ast, blackboard)? {
+                        Value::Bool(value) if value == short_circuit => {
+                            return Ok(Value::Bool(value))
+                        }
+                        Value::Bool(_) => {}
+                        value => {
+                            return Err(self.error(
+                                format!("cannot apply `{}` to {}", symbol, value.type_name()),
+                                *position,
+                            ))
+                        }
+                    }
+                }
+
+                Ok(Value::Bool(!short_circuit))
+            }
+            Ast::Binary(op, lhs, rhs, position) => {
+                let lhs = self.eval_ast(lhs, blackboard)?;
+                let rhs = self.eval_ast(rhs, blackboard)?;
+
+                self.binary(*op, lhs, rhs, *position)
+            }
+        }
+    }
+
+    fn binary(
+        &self,
+        op: BinaryOp,
+        lhs: Value,
+        rhs: Value,
+        position: usize,
+    ) -> Result<Value, ExprError> {
+        use std::cmp::Ordering;
+
+        let same_type = lhs.type_name() == rhs.type_name();
+        let ordering = match (&lhs, &rhs) {
+            (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+            (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+
+        let result = match (op, &lhs, &rhs) {
+            (BinaryOp::Eq, ..) if same_type => Value::Bool(lhs == rhs),
+            (BinaryOp::Ne, ..) if same_type => Value::Bool(lhs != rhs),
+            (BinaryOp::Lt, ..) | (BinaryOp::Le, ..) | (BinaryOp::Gt, ..) | (BinaryOp::Ge, ..)
+                if same_type && !matches!(lhs, Value::Bool(_)) =>
+            {
+                // `None` only for NaN, which compares false with anything.
+                Value::Bool(match op {
+                    BinaryOp::Lt => ordering == Some(Ordering::Less),
+                    BinaryOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    BinaryOp::Gt => ordering == Some(Ordering::Greater),
+                    _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                })
+            }
+            (BinaryOp::Add, Value::Str(a), Value::Str(b)) => Value::Str(format!("{}{}", a, b)),
+            (BinaryOp::Add, Value::Number(a), Value::Number(b)) => Value::Number(a + b),
+            (BinaryOp::Sub, Value::Number(a), Value::Number(b)) => Value::Number(a - b),
+            (BinaryOp::Mul, Value::Number(a), Value::Number(b)) => Value::Number(a * b),
+            (BinaryOp::Div, Value::Number(_), Value::Number(b))
+            | (BinaryOp::Rem, Value::Number(_), Value::Number(b))
+                if *b == 0.0 =>
+            {
+                return Err(self.error("division by zero".to_owned(), position));
+            }
+            (BinaryOp::Div, Value::Number(a), Value::Number(b)) => Value::Number(a / b),
+            (BinaryOp::Rem, Value::Number(a), Value::Number(b)) => Value::Number(a % b),
+            _ => {
+                return Err(self.error(
+                    format!(
+                        "cannot apply `{}` to {} and {}",
+                        op.symbol(),
+                        lhs.type_name(),
+                        rhs.type_name()
+                    ),
+                    position,
+                ))
+            }
+        };
+
+        Ok(result)
+    }
+
+    /// Condition for `Cond`/`While`. Evaluation errors are logged and count
+    /// as the condition being false.
+    pub fn into_condition<T: AsBlackboard + 'static>(self) -> Condition<T> {
+        Condition::Closure(Rc::new(move |data: &T| {
+            self.check(data.blackboard()).unwrap_or_else(|err| {
+                tracing::warn!("{}", err);
+                false
+            })
+        }))
+    }
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOp::Or => "||",
+            BinaryOp::And => "&&",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+        }
+    }
+}
+
+impl<T: AsBlackboard + 'static> Node<T> {
+    /// `Cond` with an [`Expression`] as its condition, e.g. for trees loaded
+    /// from data files.
+    pub fn expr_cond(
+        name: &str,
+        expression: &str,
+        success: Node<T>,
+        failure: Node<T>,
+    ) -> Result<Node<T>, ExprError> {
+        let cond = Expression::parse(expression)?.into_condition();
+
+        Ok(Self::cond_with(name, cond, success, failure))
+    }
+
+    /// `While` with an [`Expression`] as its condition.
+    pub fn expr_while(name: &str, expression: &str, child: Node<T>) -> Result<Node<T>, ExprError> {
+        let cond = Expression::parse(expression)?.into_condition();
+
+        Ok(Self::named_while_single(
+            name,
+            Box::new(move |data: &T| cond.check(data)),
+            child,
+        ))
+    }
+
+    /// `Observe` with an [`Expression`] as its condition, which is
+    /// re-evaluated whenever one of the keys used in the expression changes.
+    pub fn expr_observe(
+        name: &str,
+        expression: &str,
+        aborts: Aborts,
+        child: Node<T>,
+    ) -> Result<Node<T>, ExprError> {
+        let expression = Expression::parse(expression)?;
+        let keys: Vec<String> = expression.keys().into_iter().map(str::to_owned).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        let cond = expression.into_condition();
+
+        Ok(Self::observe(
+            name,
+            &keys,
+            aborts,
+            Box::new(move |data: &T| cond.check(data)),
+            child,
+        ))
+    }
+}
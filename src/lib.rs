@@ -1,14 +1,68 @@
+pub use crate::async_action::*;
 pub use crate::behavior::*;
 pub use crate::macros::*;
+#[cfg(feature = "bevy")]
+pub use crate::bevy::*;
+pub use crate::blackboard::*;
+#[cfg(feature = "breakpoints")]
+pub use crate::breakpoint::*;
+pub use crate::clone::*;
+#[cfg(feature = "debug-server")]
+pub use crate::debug_server::*;
+pub use crate::ecs::*;
+pub use crate::expr::*;
+pub use crate::mutate::*;
 pub use crate::node::*;
+pub use crate::path::*;
+pub use crate::reload::*;
+pub use crate::routine::*;
+#[cfg(feature = "scripting")]
+pub use crate::script::*;
+#[cfg(feature = "scripting")]
+pub use rhai;
+#[cfg(feature = "persistence")]
+pub use crate::snapshot::*;
 pub use crate::types::*;
+pub use crate::validate::*;
+pub use crate::visit::*;
 pub use crate::testing::*;
+pub use crate::tick_table::*;
+#[cfg(feature = "tui")]
+pub use crate::tui::*;
+#[cfg(feature = "derive")]
+pub use behavior_tree_derive::StatefulAction;
+#[doc(hidden)]
+pub use behavior_tree_derive::bt as __bt;
 
 // Only used internally
 mod prelude;
 
+mod async_action;
 mod behavior;
+#[cfg(feature = "bevy")]
+mod bevy;
+mod blackboard;
+#[cfg(feature = "breakpoints")]
+mod breakpoint;
+mod clone;
+#[cfg(feature = "debug-server")]
+mod debug_server;
+mod ecs;
+mod expr;
 mod macros;
+mod mutate;
 mod node;
+mod path;
+mod reload;
+mod routine;
+#[cfg(feature = "scripting")]
+mod script;
+#[cfg(feature = "persistence")]
+mod snapshot;
 mod types;
+mod validate;
+mod visit;
 mod testing;
+mod tick_table;
+#[cfg(feature = "tui")]
+mod tui;
@@ -39,5 +39,40 @@ macro_rules! maybe_profile_function {
     () => {
         #[cfg(feature = "puffin")]
         puffin::profile_function!();
-    }
+    };
+}
+
+/// Builds a whole tree from a nested description, where every node is written
+/// as `kind "name"? (args)? { children }?`, and children are separated by
+/// commas.
+///
+/// ```ignore
+/// let tree: Node<Blackboard> = bt! {
+///     select "root" {
+///         cond("has_target", |bb| bb.target.is_some()) {
+///             sequence {
+///                 action("aim", aim),
+///                 wait(0.5),
+///                 action_success("fire", fire),
+///             }
+///         } else {
+///             random_wait(2.0)
+///         },
+///         while("patrolling", |bb| bb.patrol) {
+///             stateful("patrol", Patrol::default())
+///         },
+///         observe("alarm", ["alarm"], Aborts::Both, |bb| bb.blackboard().get::<bool>("alarm") == Some(&true)) {
+///             routine("flee", flee())
+///         },
+///         port_action("move", MoveTo, [("target", "cover")]),
+///         async_action("query", |bb| query(bb)),
+///         node(YesTick::action()),
+///     }
+/// };
+/// ```
+#[macro_export]
+macro_rules! bt {
+    ($($tree:tt)*) => {
+        $crate::__bt!($crate; $($tree)*)
+    };
 }
@@ -0,0 +1,177 @@
+use crate::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+type ChildList<T> = Vec<Rc<RefCell<Node<T>>>>;
+
+/// Error returned by the runtime mutation methods on [`Node`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MutationError {
+    /// Inserting, removing and moving children is only possible on a
+    /// `Sequence` or `Select`.
+    NotComposite { kind: &'static str },
+    /// The index is past the end of the children of the node.
+    IndexOutOfBounds { index: usize, len: usize },
+    /// The running child of a `Sequence` or `Select` can't be moved.
+    RunningChild { index: usize },
+}
+
+impl std::fmt::Display for MutationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MutationError::NotComposite { kind } => {
+                write!(f, "{} is not a Sequence or Select", kind)
+            }
+            MutationError::IndexOutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "child index {} is out of bounds for {} children",
+                    index, len
+                )
+            }
+            MutationError::RunningChild { index } => {
+                write!(f, "child {} is running and can't be moved", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MutationError {}
+
+fn check_index(index: usize, len: usize) -> Result<(), MutationError> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(MutationError::IndexOutOfBounds { index, len })
+    }
+}
+
+impl<T> Node<T> {
+    fn composite_mut(&mut self) -> Result<(&mut usize, &mut ChildList<T>), MutationError> {
+        match &mut self.behavior {
+            Behavior::Sequence(current, seq) | Behavior::Select(current, seq) => Ok((current, seq)),
+            behavior => Err(MutationError::NotComposite {
+                kind: behavior.kind(),
+            }),
+        }
+    }
+
+    /// Restarts the composite when its current child was removed and no
+    /// other child took its place.
+    fn fix_current(&mut self) {
+        if let Ok((current, seq)) = self.composite_mut() {
+            if *current >= seq.len() {
+                self.reset();
+            }
+        }
+    }
+
+    /// Inserts a child into a `Sequence` or `Select`. Children inserted at or
+    /// before the running child are treated as already finished, so the
+    /// running child keeps running.
+    pub fn insert_child(&mut self, index: usize, child: Node<T>) -> Result<(), MutationError> {
+        let running = self.status == Status::Running;
+        let (current, seq) = self.composite_mut()?;
+        check_index(index, seq.len() + 1)?;
+
+        seq.insert(index, Rc::new(RefCell::new(child)));
+
+        if running && index <= *current {
+            *current += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Removes a child from a `Sequence` or `Select`. Removing the running
+    /// child resets it and continues with the one after it on the next tick.
+    pub fn remove_child(&mut self, index: usize) -> Result<Rc<RefCell<Node<T>>>, MutationError> {
+        let running = self.status == Status::Running;
+        let (current, seq) = self.composite_mut()?;
+        check_index(index, seq.len())?;
+
+        let removed = seq.remove(index);
+
+        if !running {
+            return Ok(removed);
+        }
+
+        if index < *current {
+            *current -= 1;
+        } else if index == *current {
+            removed.borrow_mut().reset();
+        }
+
+        self.fix_current();
+        Ok(removed)
+    }
+
+    /// Replaces a child of any node that has children, including the child
+    /// of a decorator (index `0`) and the branches of a `Cond` (`0` for
+    /// positive, `1` for negative). The old child is reset and returned, the
+    /// new one starts from scratch.
+    pub fn replace_child(
+        &mut self,
+        index: usize,
+        child: Node<T>,
+    ) -> Result<Rc<RefCell<Node<T>>>, MutationError> {
+        let slot = match &mut self.behavior {
+            Behavior::Sequence(_, seq) | Behavior::Select(_, seq) => {
+                check_index(index, seq.len())?;
+                &mut seq[index]
+            }
+            Behavior::Cond(_, _, positive, negative) => {
+                check_index(index, 2)?;
+                if index == 0 {
+                    positive
+                } else {
+                    negative
+                }
+            }
+            Behavior::While(_, child) | Behavior::Observe { child, .. } => {
+                check_index(index, 1)?;
+                child
+            }
+            _ => return Err(MutationError::IndexOutOfBounds { index, len: 0 }),
+        };
+
+        let old = std::mem::replace(slot, Rc::new(RefCell::new(child)));
+        old.borrow_mut().reset();
+
+        Ok(old)
+    }
+
+    /// Moves a child of a `Sequence` or `Select` to a different position.
+    /// The running child keeps running, a moved child is reset and treated
+    /// as already finished when it ends up before the running one.
+    ///
+    /// The running child itself can't be moved, as the children it would
+    /// pass are either skipped or run twice. Remove and re-insert it, or
+    /// reset the composite first.
+    pub fn move_child(&mut self, from: usize, to: usize) -> Result<(), MutationError> {
+        let running = self.status == Status::Running;
+        let (current, seq) = self.composite_mut()?;
+        check_index(from, seq.len())?;
+        check_index(to, seq.len())?;
+
+        if running && from == *current {
+            return Err(MutationError::RunningChild { index: from });
+        }
+
+        let child = seq.remove(from);
+        seq.insert(to, child.clone());
+
+        if !running {
+            return Ok(());
+        }
+
+        child.borrow_mut().reset();
+
+        if from < *current && to >= *current {
+            *current -= 1;
+        } else if from > *current && to <= *current {
+            *current += 1;
+        }
+
+        Ok(())
+    }
+}
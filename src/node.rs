@@ -1,30 +1,43 @@
+#[cfg(feature = "breakpoints")]
+use crate::breakpoint;
 use crate::maybe_profile_function;
-use crate::prelude::*;
+use crate::{
+    path::{join, NodeId},
+    prelude::*,
+};
 use std::{cell::RefCell, rc::Rc};
 
 pub struct Node<T> {
+    pub id: NodeId,
     pub name: Option<String>,
     pub behavior: Behavior<T>,
     pub status: Status,
     pub collapse_as: Option<String>,
+    /// Result reported instead of ticking the node, e.g. set by a debugger
+    /// or a test to see how the rest of the tree reacts.
+    pub forced: Option<Override>,
 }
 
 impl<T> Node<T> {
     fn new(behavior: Behavior<T>) -> Node<T> {
         Node {
+            id: NodeId::next(),
             name: None,
             behavior,
             status: Status::Initialized,
             collapse_as: None,
+            forced: None,
         }
     }
 
     pub fn new_named(name: String, behavior: Behavior<T>) -> Node<T> {
         Node {
+            id: NodeId::next(),
             name: Some(name),
             behavior,
             status: Status::Initialized,
             collapse_as: None,
+            forced: None,
         }
     }
 
@@ -39,6 +52,13 @@ impl<T> Node<T> {
         )
     }
 
+    pub fn fallible_action(name: &str, func: fn(&mut T) -> Result<Status, ActionError>) -> Node<T> {
+        Self::new_named(
+            name.to_owned(),
+            Behavior::FallibleAction(name.to_owned(), func, None),
+        )
+    }
+
     pub fn stateful_action(name: &str, func: Box<dyn StatefulAction<T>>) -> Node<T> {
         Self::new_named(
             name.to_owned(),
@@ -95,6 +115,16 @@ impl<T> Node<T> {
     }
 
     pub fn cond(name: &str, cond: fn(&T) -> bool, success: Node<T>, failure: Node<T>) -> Node<T> {
+        Self::cond_with(name, Condition::Fn(cond), success, failure)
+    }
+
+    /// `Cond` with any [`Condition`], e.g. a closure or a compiled script.
+    pub fn cond_with(
+        name: &str,
+        cond: Condition<T>,
+        success: Node<T>,
+        failure: Node<T>,
+    ) -> Node<T> {
         Self::new_named(
             name.to_owned(),
             Behavior::Cond(
@@ -130,7 +160,7 @@ impl<T> Node<T> {
     ) -> Node<T> {
         Self::new_named(
             name.to_owned(),
-            Behavior::While(cond, Rc::new(RefCell::new(child))),
+            Behavior::While(cond.into(), Rc::new(RefCell::new(child))),
         )
     }
 
@@ -139,7 +169,7 @@ impl<T> Node<T> {
         cond: Box<dyn Fn(&T) -> bool>,
         child: Rc<RefCell<Node<T>>>,
     ) -> Node<T> {
-        Self::new_named(name.to_owned(), Behavior::While(cond, child))
+        Self::new_named(name.to_owned(), Behavior::While(cond.into(), child))
     }
 
     // pub fn while_single(cond: fn(&T) -> bool, child: Node<T>) -> Node<T> {
@@ -161,12 +191,43 @@ impl<T> Node<T> {
     pub fn tick(&mut self, delta: f64, context: &mut T) -> Status {
         maybe_profile_function!();
 
-        if self.status == Status::Success || self.status == Status::Failure {
-            self.reset();
+        // Paused by a breakpoint, the node stays pending until resumed.
+        #[cfg(feature = "breakpoints")]
+        if !breakpoint::enter(self) {
+            return Status::Running;
+        }
+
+        #[cfg(feature = "breakpoints")]
+        let previous = self.status;
+
+        match self.forced {
+            Some(Override::Status(status)) => {
+                tracing::debug!(node = %self.id, name = %self.name(), ?status, "forced status");
+                self.status = status;
+            }
+            Some(Override::Skip) => {
+                tracing::debug!(node = %self.id, name = %self.name(), "skipped");
+
+                if self.status != Status::Initialized {
+                    self.reset();
+                }
+            }
+            None => {
+                if self.status == Status::Success || self.status == Status::Failure {
+                    self.reset();
+                }
+
+                self.status = self.behavior.tick(delta, context);
+            }
         }
 
-        self.status = self.behavior.tick(delta, context);
-        self.status
+        #[cfg(feature = "breakpoints")]
+        breakpoint::leave(self, previous);
+
+        match self.forced {
+            Some(Override::Skip) => Status::Success,
+            _ => self.status,
+        }
     }
 
     pub fn children(&self) -> Vec<Rc<RefCell<Node<T>>>> {
@@ -178,8 +239,10 @@ impl<T> Node<T> {
             Behavior::Select(_, ref seq) => seq.clone(),
             Behavior::Action(_, _) => vec![],
             Behavior::ActionSuccess(_, _) => vec![],
+            Behavior::FallibleAction(_, _, _) => vec![],
             Behavior::StatefulAction(_, _) => vec![],
             Behavior::While(_, item) => vec![item.clone()],
+            Behavior::Observe { child, .. } => vec![child.clone()],
         }
     }
 
@@ -209,6 +272,7 @@ impl<T> Node<T> {
                     Behavior::Select(_, _seq) => "Select".to_string(),
                     Behavior::Action(name, _) => format!("Action {}", name),
                     Behavior::ActionSuccess(name, _) => format!("ActionSuccess {}", name),
+                    Behavior::FallibleAction(name, _, _) => format!("FallibleAction {}", name),
                     Behavior::StatefulAction(name, _) => format!("StatefulAction {}", name),
                     // Behavior::While(_, x) => TreeRepr::new("While", vec![x.to_debug()]),
                     // TODO: add to detail
@@ -218,16 +282,67 @@ impl<T> Node<T> {
                             self.name.as_ref().expect("While must have a name")
                         )
                     }
+                    Behavior::Observe { .. } => {
+                        format!(
+                            "Observe {}",
+                            self.name.as_ref().expect("Observe must have a name")
+                        )
+                    }
                 }
             }
         }
     }
 
+    /// Seconds after which the running part of this subtree needs another
+    /// tick, see [`BehaviorTree::next_wakeup`].
+    pub fn next_wakeup(&self) -> Option<f64> {
+        if self.status != Status::Running {
+            return None;
+        }
+
+        match &self.behavior {
+            Behavior::Wait { curr, .. } => Some(curr.max(0.0)),
+            Behavior::RandomWait { curr, .. } => Some(curr.max(0.0)),
+            Behavior::Action(_, _) => Some(0.0),
+            Behavior::ActionSuccess(_, _) => Some(0.0),
+            Behavior::FallibleAction(_, _, _) => Some(0.0),
+            Behavior::StatefulAction(_, action) => action.wakeup(),
+            Behavior::Sequence(current, seq) | Behavior::Select(current, seq) => seq
+                .get(*current)
+                .and_then(|node| node.borrow().next_wakeup()),
+            _ => self
+                .iter_children()
+                .filter_map(|node| node.borrow().next_wakeup())
+                .reduce(f64::min),
+        }
+    }
+
+    /// Collects errors left behind by fallible actions in this subtree.
+    pub(crate) fn take_errors(&mut self, path: &str, errors: &mut Vec<TickError>) {
+        if let Behavior::FallibleAction(_, _, error) = &mut self.behavior {
+            if let Some(error) = error.take() {
+                errors.push(TickError {
+                    path: path.to_owned(),
+                    error,
+                });
+            }
+        }
+
+        for (child, segment) in self.iter_children().zip(self.child_segments()) {
+            child
+                .borrow_mut()
+                .take_errors(&join(path, &segment), errors);
+        }
+    }
+
     pub fn recheck_condition(&mut self, context: &T, is_sequence: bool) -> bool {
         maybe_profile_function!();
 
         match &self.behavior {
             Behavior::While(cond, _) => cond(context) != is_sequence,
+            Behavior::Observe { aborts, .. } if aborts.lower_priority() => {
+                self.behavior.observe_changed(context) == Some(!is_sequence)
+            }
             _ => false,
             // Behavior::Sequence(_, _) => todo!(),
             // Behavior::Select(_, _) => todo!(),
@@ -0,0 +1,158 @@
+use crate::prelude::*;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Identifier of a node. [`BehaviorTree::new`] and
+/// [`BehaviorTree::hot_reload`] number the nodes depth first starting from 0,
+/// so the same tree gets the same ids in every run and every copy made by
+/// `clone_tree`, and they can be stored in logs or save files.
+///
+/// Nodes that aren't part of a tree yet, including children inserted into a
+/// running tree, have a temporary id from [`NodeId::next`] until the tree is
+/// built again.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(pub u64);
+
+/// Set on temporary ids, keeping them apart from the ids of tree nodes.
+const TEMPORARY: u64 = 1 << 63;
+
+impl NodeId {
+    /// Temporary id, unique within the process.
+    pub fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        NodeId(TEMPORARY | NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn is_temporary(self) -> bool {
+        self.0 & TEMPORARY != 0
+    }
+}
+
+impl std::fmt::Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Appends a segment returned by [`Node::child_segments`] to a path.
+pub(crate) fn join(path: &str, segment: &str) -> String {
+    format!("{}/{}", path, segment)
+}
+
+impl<T> Node<T> {
+    /// Path segments of the children of this node. Named children use their
+    /// name (e.g. `Attack`) unless a sibling shares it, unnamed children and
+    /// children with duplicate names also get their index (e.g.
+    /// `Sequence[2]`).
+    ///
+    /// A full path is made of segments joined by `/`, starting with `root`
+    /// for the root node, e.g. `root/Sequence[2]/Attack`.
+    pub fn child_segments(&self) -> Vec<String> {
+        let labels: Vec<(Option<String>, &'static str)> = self
+            .iter_children()
+            .map(|child| match child.try_borrow() {
+                Ok(child) => (child.name.clone(), child.behavior.kind()),
+                // Nodes which are being ticked right now can't be inspected.
+                Err(_) => (None, "Node"),
+            })
+            .collect();
+
+        labels
+            .iter()
+            .enumerate()
+            .map(|(i, (name, kind))| match name {
+                Some(name)
+                    if labels
+                        .iter()
+                        .filter(|(n, _)| n.as_ref() == Some(name))
+                        .count()
+                        == 1 =>
+                {
+                    name.clone()
+                }
+                Some(name) => format!("{}[{}]", name, i),
+                None => format!("{}[{}]", kind, i),
+            })
+            .collect()
+    }
+}
+
+impl<T> BehaviorTree<T> {
+    /// Gives the nodes their ids, in depth first order.
+    pub(crate) fn number_nodes(&self) {
+        for (i, (_, node)) in self.nodes().into_iter().enumerate() {
+            node.borrow_mut().id = NodeId(i as u64);
+        }
+    }
+
+    /// All nodes of the tree in depth first order, along with their paths.
+    pub fn nodes(&self) -> Vec<(String, Rc<RefCell<Node<T>>>)> {
+        let mut result = vec![];
+        let mut stack = vec![("root".to_owned(), self.tree.clone())];
+
+        while let Some((path, node)) = stack.pop() {
+            let segments = node.borrow().child_segments();
+
+            for (child, segment) in node.borrow().iter_children().zip(segments).rev() {
+                stack.push((join(&path, &segment), child.clone()));
+            }
+
+            result.push((path, node));
+        }
+
+        result
+    }
+
+    pub fn find_by_id(&self, id: NodeId) -> Option<Rc<RefCell<Node<T>>>> {
+        self.nodes()
+            .into_iter()
+            .map(|(_, node)| node)
+            .find(|node| node.borrow().id == id)
+    }
+
+    /// Finds a node by a path like `root/Sequence[2]/Attack`, see
+    /// [`Node::child_segments`].
+    pub fn find_by_path(&self, path: &str) -> Option<Rc<RefCell<Node<T>>>> {
+        let mut segments = path.split('/');
+
+        if segments.next() != Some("root") {
+            return None;
+        }
+
+        let mut node = self.tree.clone();
+
+        for segment in segments {
+            let index = node
+                .borrow()
+                .child_segments()
+                .iter()
+                .position(|s| s == segment)?;
+            let child = node.borrow().iter_children().nth(index)?.clone();
+            node = child;
+        }
+
+        Some(node)
+    }
+
+    /// All nodes with the given name, in depth first order.
+    pub fn find_by_name(&self, name: &str) -> Vec<Rc<RefCell<Node<T>>>> {
+        self.nodes()
+            .into_iter()
+            .map(|(_, node)| node)
+            .filter(|node| node.borrow().name.as_deref() == Some(name))
+            .collect()
+    }
+
+    /// Path of the node with the given id.
+    pub fn path_of(&self, id: NodeId) -> Option<String> {
+        self.nodes()
+            .into_iter()
+            .find(|(_, node)| node.borrow().id == id)
+            .map(|(path, _)| path)
+    }
+}
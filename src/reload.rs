@@ -0,0 +1,151 @@
+use crate::{path::join, prelude::*};
+use std::{cell::RefCell, rc::Rc};
+
+/// Result of [`BehaviorTree::hot_reload`], listing node paths in the new tree
+/// (e.g. `root/Sequence[1]/Attack`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Nodes whose runtime state was carried over from the old tree.
+    pub kept: Vec<String>,
+    /// Roots of branches that start from scratch, either because they are
+    /// new or because they no longer match the old tree.
+    pub reset: Vec<String>,
+}
+
+impl<T> BehaviorTree<T> {
+    /// Replaces the root of the tree with a new definition while keeping the
+    /// runtime state of nodes which have the same path, kind and name in both
+    /// trees. The new definition of a stateful action that already ran picks
+    /// up the state of the old one through
+    /// [`StatefulAction::hand_over_state`] and `take_over_state`, or
+    /// `save_state` and `load_state` with the `persistence` feature.
+    /// Otherwise it starts from scratch and is listed under `reset`.
+    ///
+    /// A composite only keeps its state when the children up to the running
+    /// one are unchanged, otherwise its whole branch is reset.
+    pub fn hot_reload(&mut self, root: Node<T>) -> ReloadReport {
+        let mut report = ReloadReport::default();
+        let root = Rc::new(RefCell::new(root));
+
+        transfer(
+            &mut self.tree.borrow_mut(),
+            &mut root.borrow_mut(),
+            "root",
+            &mut report,
+        );
+
+        self.tree = root;
+        self.number_nodes();
+        report
+    }
+}
+
+fn matches<T>(old: &Node<T>, new: &Node<T>) -> bool {
+    old.behavior.kind() == new.behavior.kind() && old.name == new.name
+}
+
+fn transfer<T>(old: &mut Node<T>, new: &mut Node<T>, path: &str, report: &mut ReloadReport) {
+    if !matches(old, new) {
+        report.reset.push(path.to_owned());
+        return;
+    }
+
+    let old_children = old.children();
+    let new_children = new.children();
+
+    let compatible = match (&old.behavior, &new.behavior) {
+        (Behavior::Sequence(current, _), Behavior::Sequence(..))
+        | (Behavior::Select(current, _), Behavior::Select(..)) => {
+            // Finished composites start over on their next tick anyway.
+            old.status != Status::Running
+                || (*current < new_children.len()
+                    && old_children
+                        .iter()
+                        .zip(&new_children)
+                        .take(*current + 1)
+                        .all(|(old, new)| matches(&old.borrow(), &new.borrow())))
+        }
+        _ => old_children.len() == new_children.len(),
+    };
+
+    if !compatible {
+        report.reset.push(path.to_owned());
+        return;
+    }
+
+    if let (Behavior::StatefulAction(_, action), Behavior::StatefulAction(_, new_action)) =
+        (&mut old.behavior, &mut new.behavior)
+    {
+        if old.status != Status::Initialized && !carry_over(action.as_mut(), new_action.as_mut()) {
+            new_action.reset();
+            report.reset.push(path.to_owned());
+            return;
+        }
+    }
+
+    new.status = old.status;
+
+    match (&mut old.behavior, &mut new.behavior) {
+        (
+            Behavior::Wait { curr, .. },
+            Behavior::Wait {
+                curr: new_curr,
+                max,
+            },
+        ) => {
+            *new_curr = curr.min(*max);
+        }
+        (
+            Behavior::RandomWait { curr, curr_max, .. },
+            Behavior::RandomWait {
+                curr: new_curr,
+                curr_max: new_curr_max,
+                max,
+            },
+        ) => {
+            *new_curr_max = curr_max.min(*max);
+            *new_curr = curr.min(*new_curr_max);
+        }
+        (Behavior::Sequence(current, _), Behavior::Sequence(new_current, _))
+        | (Behavior::Select(current, _), Behavior::Select(new_current, _)) => {
+            *new_current = *current;
+        }
+        _ => {}
+    }
+
+    report.kept.push(path.to_owned());
+
+    for (i, (new_child, segment)) in new_children.iter().zip(new.child_segments()).enumerate() {
+        let child_path = join(path, &segment);
+
+        match old_children.get(i) {
+            Some(old_child) => transfer(
+                &mut old_child.borrow_mut(),
+                &mut new_child.borrow_mut(),
+                &child_path,
+                report,
+            ),
+            None => report.reset.push(child_path),
+        }
+    }
+}
+
+fn carry_over<T>(old: &mut dyn StatefulAction<T>, new: &mut dyn StatefulAction<T>) -> bool {
+    match old.hand_over_state() {
+        Some(state) => new.take_over_state(state),
+        None => carry_over_saved(old, new),
+    }
+}
+
+#[cfg(feature = "persistence")]
+fn carry_over_saved<T>(old: &mut dyn StatefulAction<T>, new: &mut dyn StatefulAction<T>) -> bool {
+    match old.save_state() {
+        Some(state) => new.load_state(state).is_ok(),
+        None => false,
+    }
+}
+
+#[cfg(not(feature = "persistence"))]
+fn carry_over_saved<T>(_old: &mut dyn StatefulAction<T>, _new: &mut dyn StatefulAction<T>) -> bool {
+    false
+}
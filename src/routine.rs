@@ -0,0 +1,132 @@
+use crate::prelude::*;
+
+enum Step<T> {
+    Do(Box<dyn FnMut(&mut T) -> Status>),
+    Wait(f64),
+}
+
+/// Action made of a list of steps which are executed one after another,
+/// picking up where the previous tick left off. This makes multi-step
+/// actions like "walk to the door, wait 2s, open it" possible without
+/// writing a `StatefulAction` state machine by hand.
+///
+/// A step that fails makes the whole routine fail, and the routine starts
+/// over from the first step once it finished or was reset.
+pub struct Routine<T> {
+    steps: Vec<Step<T>>,
+    current: usize,
+    waited: f64,
+}
+
+impl<T> Default for Routine<T> {
+    fn default() -> Self {
+        Self {
+            steps: vec![],
+            current: 0,
+            waited: 0.0,
+        }
+    }
+}
+
+impl<T> Routine<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a step which is ticked until it stops returning `Running`.
+    pub fn then(mut self, step: impl FnMut(&mut T) -> Status + 'static) -> Self {
+        self.steps.push(Step::Do(Box::new(step)));
+        self
+    }
+
+    /// Adds a delay measured in the `delta` passed to the tree.
+    pub fn wait(mut self, time: f64) -> Self {
+        self.steps.push(Step::Wait(time));
+        self
+    }
+
+    /// Index of the step that will be executed on the next tick.
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+}
+
+impl<T> StatefulAction<T> for Routine<T> {
+    fn tick(&mut self, data: &mut T) -> Status {
+        self.tick_delta(0.0, data)
+    }
+
+    fn tick_delta(&mut self, mut delta: f64, data: &mut T) -> Status {
+        while self.current < self.steps.len() {
+            match &mut self.steps[self.current] {
+                Step::Do(step) => match step(data) {
+                    Status::Success => {}
+                    Status::Running => return Status::Running,
+                    status => return status,
+                },
+                Step::Wait(time) => {
+                    // Time only passes once per tick even with multiple
+                    // waits in a row.
+                    self.waited += delta;
+                    delta = 0.0;
+
+                    if self.waited < *time {
+                        return Status::Running;
+                    }
+                    self.waited = 0.0;
+                }
+            }
+
+            self.current += 1;
+        }
+
+        Status::Success
+    }
+
+    fn reset(&mut self) {
+        self.current = 0;
+        self.waited = 0.0;
+    }
+
+    fn wakeup(&self) -> Option<f64> {
+        match self.steps.get(self.current) {
+            Some(Step::Wait(time)) => Some((time - self.waited).max(0.0)),
+            _ => Some(0.0),
+        }
+    }
+
+    fn hand_over_state(&mut self) -> Option<Box<dyn std::any::Any>> {
+        Some(Box::new((self.current, self.waited)))
+    }
+
+    fn take_over_state(&mut self, state: Box<dyn std::any::Any>) -> bool {
+        match state.downcast::<(usize, f64)>() {
+            Ok(state) => {
+                let (current, waited) = *state;
+                self.current = current.min(self.steps.len());
+                self.waited = waited;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(feature = "persistence")]
+    fn save_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value((self.current, self.waited)).ok()
+    }
+
+    #[cfg(feature = "persistence")]
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        let (current, waited): (usize, f64) = serde_json::from_value(state)?;
+        self.current = current.min(self.steps.len());
+        self.waited = waited;
+        Ok(())
+    }
+}
+
+impl<T: 'static> Node<T> {
+    pub fn routine(name: &str, routine: Routine<T>) -> Node<T> {
+        Self::stateful_action(name, Box::new(routine))
+    }
+}
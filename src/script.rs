@@ -0,0 +1,250 @@
+use crate::prelude::*;
+use crate::{AsBlackboard, Blackboard};
+use rhai::{Dynamic, Engine, Scope, AST};
+use std::rc::Rc;
+
+/// Error returned when building a node from a script that doesn't compile.
+#[derive(Debug)]
+pub struct ScriptError {
+    /// Name of the node being built.
+    pub name: String,
+    pub error: rhai::ParseError,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "script of {} failed to compile: {}",
+            self.name, self.error
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Builds nodes from [Rhai](https://rhai.rs) scripts, which are compiled
+/// when the node is built and evaluated against the blackboard of the
+/// context on every tick.
+///
+/// Blackboard entries of type `i64`, `i32`, `f64`, `f32`, `bool`, `String`
+/// and `&'static str` are available to scripts as variables, other entries
+/// are not visible. Actions can assign to these variables, which writes the
+/// new value back to the blackboard (except for `&'static str`).
+#[derive(Clone)]
+pub struct ScriptEngine {
+    engine: Rc<Engine>,
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptEngine {
+    pub fn new() -> Self {
+        Self::with_engine(Engine::new())
+    }
+
+    /// Uses a preconfigured engine, e.g. with custom functions registered.
+    pub fn with_engine(mut engine: Engine) -> Self {
+        engine.register_type_with_name::<Status>("Status");
+
+        Self {
+            engine: Rc::new(engine),
+        }
+    }
+
+    fn compile_condition<T: AsBlackboard + 'static>(
+        &self,
+        name: &str,
+        script: &str,
+    ) -> Result<Condition<T>, ScriptError> {
+        let ast = self
+            .engine
+            .compile_expression(script)
+            .map_err(|error| ScriptError {
+                name: name.to_owned(),
+                error,
+            })?;
+
+        let engine = self.engine.clone();
+        let name = name.to_owned();
+
+        Ok(Condition::Closure(Rc::new(move |data: &T| {
+            let mut scope = scope(data.blackboard());
+
+            match engine.eval_ast_with_scope::<bool>(&mut scope, &ast) {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::warn!("condition {} failed: {}", name, err);
+                    false
+                }
+            }
+        })))
+    }
+
+    /// `Cond` whose condition is a script expression such as
+    /// `health < 30 && ammo > 0`.
+    pub fn cond<T: AsBlackboard + 'static>(
+        &self,
+        name: &str,
+        script: &str,
+        success: Node<T>,
+        failure: Node<T>,
+    ) -> Result<Node<T>, ScriptError> {
+        let cond = self.compile_condition(name, script)?;
+
+        Ok(Node::cond_with(name, cond, success, failure))
+    }
+
+    /// `While` whose condition is a script expression.
+    pub fn while_single<T: AsBlackboard + 'static>(
+        &self,
+        name: &str,
+        script: &str,
+        child: Node<T>,
+    ) -> Result<Node<T>, ScriptError> {
+        let cond = self.compile_condition(name, script)?;
+
+        Ok(Node::named_while_single(
+            name,
+            Box::new(move |data: &T| cond.check(data)),
+            child,
+        ))
+    }
+
+    /// Action running a script. Its value decides the status: `()` and `true`
+    /// succeed, `false` fails, and the `SUCCESS`, `FAILURE` and `RUNNING`
+    /// constants map to the respective status. Scripts that fail at runtime
+    /// or return anything else fail.
+    pub fn action<T: AsBlackboard + 'static>(
+        &self,
+        name: &str,
+        script: &str,
+    ) -> Result<Node<T>, ScriptError> {
+        let ast = self.engine.compile(script).map_err(|error| ScriptError {
+            name: name.to_owned(),
+            error,
+        })?;
+
+        Ok(Node::stateful_action(
+            name,
+            Box::new(ScriptAction {
+                name: name.to_owned(),
+                engine: self.engine.clone(),
+                ast,
+            }),
+        ))
+    }
+}
+
+#[derive(Clone)]
+struct ScriptAction {
+    name: String,
+    engine: Rc<Engine>,
+    ast: AST,
+}
+
+impl<T: AsBlackboard> StatefulAction<T> for ScriptAction {
+    fn tick(&mut self, data: &mut T) -> Status {
+        let blackboard = data.blackboard_mut();
+        let mut scope = scope(blackboard);
+        scope.push_constant("SUCCESS", Status::Success);
+        scope.push_constant("FAILURE", Status::Failure);
+        scope.push_constant("RUNNING", Status::Running);
+
+        let result = self
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast);
+
+        for (key, _, value) in scope.iter_raw() {
+            write_back(blackboard, key, value);
+        }
+
+        match result {
+            Ok(value) if value.is_unit() => Status::Success,
+            Ok(value) => match value.as_bool() {
+                Ok(true) => Status::Success,
+                Ok(false) => Status::Failure,
+                Err(_) => value.try_cast::<Status>().unwrap_or(Status::Failure),
+            },
+            Err(err) => {
+                tracing::warn!("action {} failed: {}", self.name, err);
+                Status::Failure
+            }
+        }
+    }
+
+    fn reset(&mut self) {}
+
+    fn box_clone(&self) -> Option<Box<dyn StatefulAction<T>>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+fn scope(blackboard: &Blackboard) -> Scope<'static> {
+    let mut scope = Scope::new();
+
+    for key in blackboard.keys() {
+        if let Some(value) = read(blackboard, key) {
+            scope.push_dynamic(key, value);
+        }
+    }
+
+    scope
+}
+
+fn read(blackboard: &Blackboard, key: &str) -> Option<Dynamic> {
+    if let Some(value) = blackboard.get::<i64>(key) {
+        Some((*value).into())
+    } else if let Some(value) = blackboard.get::<i32>(key) {
+        Some((*value as i64).into())
+    } else if let Some(value) = blackboard.get::<f64>(key) {
+        Some((*value).into())
+    } else if let Some(value) = blackboard.get::<f32>(key) {
+        Some((*value as f64).into())
+    } else if let Some(value) = blackboard.get::<bool>(key) {
+        Some((*value).into())
+    } else if let Some(value) = blackboard.get::<String>(key) {
+        Some(value.clone().into())
+    } else {
+        blackboard
+            .get::<&'static str>(key)
+            .map(|value| (*value).into())
+    }
+}
+
+macro_rules! write_back_as {
+    ($blackboard:ident, $key:ident, $ty:ty, $new:expr) => {
+        if let Some(old) = $blackboard.get::<$ty>($key) {
+            if let Some(new) = $new {
+                // Only actual changes bump the revision of the key.
+                if *old != new {
+                    $blackboard.set::<$ty>($key, new);
+                }
+            }
+            return;
+        }
+    };
+}
+
+fn write_back(blackboard: &mut Blackboard, key: &str, value: &Dynamic) {
+    write_back_as!(blackboard, key, i64, value.as_int().ok());
+    write_back_as!(blackboard, key, i32, value.as_int().ok().map(|v| v as i32));
+    // Integer literals can be assigned to float entries.
+    let float = value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|v| v as f64));
+
+    write_back_as!(blackboard, key, f64, float);
+    write_back_as!(blackboard, key, f32, float.map(|v| v as f32));
+    write_back_as!(blackboard, key, bool, value.as_bool().ok());
+    write_back_as!(blackboard, key, String, value.clone().into_string().ok());
+}
@@ -0,0 +1,246 @@
+use crate::{path::join, prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Runtime state of a tree, which can be serialized into a save game and
+/// later restored onto a tree with the same structure.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeSnapshot {
+    pub kind: String,
+    pub name: Option<String>,
+    pub status: Status,
+    pub state: BehaviorState,
+    pub children: Vec<NodeSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BehaviorState {
+    Stateless,
+    Wait { curr: f64 },
+    RandomWait { curr: f64, curr_max: f64 },
+    Composite { current: usize },
+    StatefulAction(Option<serde_json::Value>),
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The node at `path` doesn't match the one the snapshot was taken from.
+    StructureMismatch {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    /// A stateful action failed to load its state.
+    ActionState {
+        path: String,
+        error: serde_json::Error,
+    },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::StructureMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "snapshot doesn't match the tree at {}: expected {}, found {}",
+                path, expected, found
+            ),
+            SnapshotError::ActionState { path, error } => {
+                write!(f, "failed to restore action state at {}: {}", path, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl NodeSnapshot {
+    fn describe(&self) -> String {
+        describe(&self.kind, &self.name, self.children.len())
+    }
+}
+
+fn describe(kind: &str, name: &Option<String>, children: usize) -> String {
+    match name {
+        Some(name) => format!("{} {:?} with {} children", kind, name, children),
+        None => format!("{} with {} children", kind, children),
+    }
+}
+
+impl<T> Node<T> {
+    pub fn snapshot(&self) -> NodeSnapshot {
+        let state = match &self.behavior {
+            Behavior::Wait { curr, .. } => BehaviorState::Wait { curr: *curr },
+            Behavior::RandomWait { curr, curr_max, .. } => BehaviorState::RandomWait {
+                curr: *curr,
+                curr_max: *curr_max,
+            },
+            Behavior::Sequence(current, _) | Behavior::Select(current, _) => {
+                BehaviorState::Composite { current: *current }
+            }
+            Behavior::StatefulAction(_, action) => {
+                BehaviorState::StatefulAction(action.save_state())
+            }
+            _ => BehaviorState::Stateless,
+        };
+
+        NodeSnapshot {
+            kind: self.behavior.kind().to_owned(),
+            name: self.name.clone(),
+            status: self.status,
+            state,
+            children: self
+                .children()
+                .iter()
+                .map(|child| child.borrow().snapshot())
+                .collect(),
+        }
+    }
+
+    /// Restores the runtime state stored in `snapshot`. Nothing is modified
+    /// unless the whole tree matches the structure of the snapshot and every
+    /// stateful action accepts its state. Actions which loaded their state
+    /// before another one failed are rolled back with the state they returned
+    /// from `save_state` beforehand.
+    pub fn restore(&mut self, snapshot: &NodeSnapshot) -> Result<(), SnapshotError> {
+        self.check_structure(snapshot, "root")?;
+
+        let mut previous = vec![];
+        let loaded = self.for_each_action(snapshot, "root", &mut |path, action, saved| {
+            if let Some(saved) = saved {
+                previous.push((path.to_owned(), action.save_state()));
+                action.reset();
+                action
+                    .load_state(saved.clone())
+                    .map_err(|error| SnapshotError::ActionState {
+                        path: path.to_owned(),
+                        error,
+                    })?;
+            }
+            Ok(())
+        });
+
+        if let Err(error) = loaded {
+            let _ = self.for_each_action(snapshot, "root", &mut |path, action, _| {
+                if let Some((_, state)) = previous.iter().find(|(p, _)| p == path) {
+                    action.reset();
+                    if let Some(state) = state {
+                        // The state was just saved by the same action.
+                        let _ = action.load_state(state.clone());
+                    }
+                }
+                Ok(())
+            });
+
+            return Err(error);
+        }
+
+        self.apply_snapshot(snapshot);
+        Ok(())
+    }
+
+    fn check_structure(&self, snapshot: &NodeSnapshot, path: &str) -> Result<(), SnapshotError> {
+        let children = self.children();
+
+        if snapshot.kind != self.behavior.kind()
+            || snapshot.name != self.name
+            || snapshot.children.len() != children.len()
+        {
+            return Err(SnapshotError::StructureMismatch {
+                path: path.to_owned(),
+                expected: snapshot.describe(),
+                found: describe(self.behavior.kind(), &self.name, children.len()),
+            });
+        }
+
+        for ((child, child_snapshot), segment) in children
+            .iter()
+            .zip(&snapshot.children)
+            .zip(self.child_segments())
+        {
+            child
+                .borrow()
+                .check_structure(child_snapshot, &join(path, &segment))?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `f` with every stateful action in the subtree along with its
+    /// saved state, stopping at the first error.
+    fn for_each_action(
+        &mut self,
+        snapshot: &NodeSnapshot,
+        path: &str,
+        f: &mut ActionStateFn<T>,
+    ) -> Result<(), SnapshotError> {
+        if let (Behavior::StatefulAction(_, action), BehaviorState::StatefulAction(saved)) =
+            (&mut self.behavior, &snapshot.state)
+        {
+            f(path, action.as_mut(), saved)?;
+        }
+
+        for ((child, child_snapshot), segment) in self
+            .children()
+            .iter()
+            .zip(&snapshot.children)
+            .zip(self.child_segments())
+        {
+            child
+                .borrow_mut()
+                .for_each_action(child_snapshot, &join(path, &segment), f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies everything except the state of stateful actions, which were
+    /// loaded by `restore` already.
+    fn apply_snapshot(&mut self, snapshot: &NodeSnapshot) {
+        self.status = snapshot.status;
+
+        match (&mut self.behavior, &snapshot.state) {
+            (Behavior::Wait { curr, .. }, BehaviorState::Wait { curr: saved }) => {
+                *curr = *saved;
+            }
+            (
+                Behavior::RandomWait { curr, curr_max, .. },
+                BehaviorState::RandomWait {
+                    curr: saved,
+                    curr_max: saved_max,
+                },
+            ) => {
+                *curr = *saved;
+                *curr_max = *saved_max;
+            }
+            (Behavior::Sequence(current, _), BehaviorState::Composite { current: saved })
+            | (Behavior::Select(current, _), BehaviorState::Composite { current: saved }) => {
+                *current = *saved;
+            }
+            (Behavior::StatefulAction(_, action), BehaviorState::StatefulAction(None)) => {
+                action.reset();
+            }
+            _ => {}
+        }
+
+        for (child, child_snapshot) in self.children().iter().zip(&snapshot.children) {
+            child.borrow_mut().apply_snapshot(child_snapshot);
+        }
+    }
+}
+
+type ActionStateFn<'a, T> = dyn FnMut(&str, &mut dyn StatefulAction<T>, &Option<serde_json::Value>) -> Result<(), SnapshotError>
+    + 'a;
+
+impl<T> BehaviorTree<T> {
+    pub fn snapshot(&self) -> NodeSnapshot {
+        self.tree.borrow().snapshot()
+    }
+
+    pub fn restore(&mut self, snapshot: &NodeSnapshot) -> Result<(), SnapshotError> {
+        self.tree.borrow_mut().restore(snapshot)
+    }
+}
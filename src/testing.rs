@@ -133,3 +133,159 @@ impl<T> StatefulAction<T> for Counter {
         }
     }
 }
+
+/// What a [`ScriptedAction`] returns once it went through all of its
+/// statuses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OnExhausted {
+    RepeatLast,
+    /// Starts over from the first status.
+    Loop,
+    Return(Status),
+    Panic,
+}
+
+/// What resetting a [`ScriptedAction`] does to its position in the script.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OnReset {
+    Restart,
+    Continue,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScriptedCall {
+    Tick(Status),
+    Reset,
+}
+
+/// Calls recorded by a [`ScriptedAction`], shared with the test holding it.
+#[derive(Clone, Default)]
+pub struct ScriptLog(Rc<RefCell<Vec<ScriptedCall>>>);
+
+impl ScriptLog {
+    pub fn calls(&self) -> Vec<ScriptedCall> {
+        self.0.borrow().clone()
+    }
+
+    /// Statuses returned so far, in order.
+    pub fn ticks(&self) -> Vec<Status> {
+        self.0
+            .borrow()
+            .iter()
+            .filter_map(|call| match call {
+                ScriptedCall::Tick(status) => Some(*status),
+                ScriptedCall::Reset => None,
+            })
+            .collect()
+    }
+
+    pub fn tick_count(&self) -> usize {
+        self.ticks().len()
+    }
+
+    pub fn reset_count(&self) -> usize {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|call| **call == ScriptedCall::Reset)
+            .count()
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+}
+
+/// Node returning a predefined list of statuses, one per tick, and
+/// recording every tick and reset in a [`ScriptLog`].
+///
+/// By default it repeats the last status once the list is exhausted and
+/// starts over from the first one when reset.
+pub struct ScriptedAction {
+    statuses: Vec<Status>,
+    position: usize,
+    on_exhausted: OnExhausted,
+    on_reset: OnReset,
+    log: ScriptLog,
+}
+
+impl ScriptedAction {
+    pub fn new(statuses: Vec<Status>) -> Self {
+        Self {
+            statuses,
+            position: 0,
+            on_exhausted: OnExhausted::RepeatLast,
+            on_reset: OnReset::Restart,
+            log: ScriptLog::default(),
+        }
+    }
+
+    pub fn on_exhausted(self, on_exhausted: OnExhausted) -> Self {
+        Self {
+            on_exhausted,
+            ..self
+        }
+    }
+
+    pub fn on_reset(self, on_reset: OnReset) -> Self {
+        Self { on_reset, ..self }
+    }
+
+    pub fn log(&self) -> ScriptLog {
+        self.log.clone()
+    }
+
+    pub fn node<T>(self, name: &str) -> Node<T> {
+        Node::stateful_action(name, Box::new(self))
+    }
+
+    /// Shorthand for a node with the default behaviour along with its log.
+    pub fn action<T>(statuses: Vec<Status>) -> (Node<T>, ScriptLog) {
+        let action = Self::new(statuses);
+        let log = action.log();
+        (action.node("scripted"), log)
+    }
+
+    fn next_status(&mut self) -> Status {
+        if let Some(status) = self.statuses.get(self.position) {
+            self.position += 1;
+            return *status;
+        }
+
+        match self.on_exhausted {
+            OnExhausted::RepeatLast => *self
+                .statuses
+                .last()
+                .expect("ScriptedAction without statuses can't repeat the last one"),
+            OnExhausted::Loop => {
+                assert!(
+                    !self.statuses.is_empty(),
+                    "ScriptedAction without statuses can't loop"
+                );
+                self.position = 1;
+                self.statuses[0]
+            }
+            OnExhausted::Return(status) => status,
+            OnExhausted::Panic => panic!(
+                "ScriptedAction ticked after returning all {} statuses",
+                self.statuses.len()
+            ),
+        }
+    }
+}
+
+impl<T> StatefulAction<T> for ScriptedAction {
+    fn tick(&mut self, _data: &mut T) -> Status {
+        let status = self.next_status();
+        self.log.0.borrow_mut().push(ScriptedCall::Tick(status));
+        status
+    }
+
+    fn reset(&mut self) {
+        self.log.0.borrow_mut().push(ScriptedCall::Reset);
+
+        if self.on_reset == OnReset::Restart {
+            self.position = 0;
+        }
+    }
+}
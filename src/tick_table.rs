@@ -0,0 +1,231 @@
+use crate::prelude::*;
+use std::fmt::Write;
+
+type Setup<T> = Box<dyn FnOnce(&mut T)>;
+
+/// One row of a [`TickTable`]: how to tick the tree and what to expect
+/// afterwards. Expectations that aren't set aren't checked.
+pub struct TickRow<T> {
+    delta: f64,
+    setup: Option<Setup<T>>,
+    status: Option<Status>,
+    active: Option<Option<String>>,
+    nodes: Vec<(String, Status)>,
+}
+
+impl<T> TickRow<T> {
+    pub fn new(delta: f64) -> Self {
+        Self {
+            delta,
+            setup: None,
+            status: None,
+            active: None,
+            nodes: vec![],
+        }
+    }
+
+    /// Changes the context (e.g. the blackboard) before the tick.
+    pub fn with(self, setup: impl FnOnce(&mut T) + 'static) -> Self {
+        Self {
+            setup: Some(Box::new(setup)),
+            ..self
+        }
+    }
+
+    /// Status the root returns.
+    pub fn expect(self, status: Status) -> Self {
+        Self {
+            status: Some(status),
+            ..self
+        }
+    }
+
+    /// Path of the deepest running node, see [`BehaviorTree::active_path`].
+    pub fn active(self, path: &str) -> Self {
+        Self {
+            active: Some(Some(path.to_owned())),
+            ..self
+        }
+    }
+
+    /// Nothing is left running after the tick.
+    pub fn inactive(self) -> Self {
+        Self {
+            active: Some(None),
+            ..self
+        }
+    }
+
+    pub fn node(mut self, path: &str, status: Status) -> Self {
+        self.nodes.push((path.to_owned(), status));
+        self
+    }
+}
+
+/// Ticks a tree row by row and checks the expectations of each
+/// [`TickRow`], stopping at the first tick that doesn't match:
+///
+/// ```ignore
+/// TickTable::new(vec![
+///     TickRow::new(1.0).expect(Running).active("root/Wait[0]"),
+///     TickRow::new(1.0).with(|bb: &mut Blackboard| bb.cond = false).expect(Failure),
+/// ])
+/// .assert(&mut bt, &mut bb);
+/// ```
+pub struct TickTable<T> {
+    rows: Vec<TickRow<T>>,
+}
+
+impl<T> TickTable<T> {
+    pub fn new(rows: Vec<TickRow<T>>) -> Self {
+        Self { rows }
+    }
+
+    pub fn run(self, tree: &mut BehaviorTree<T>, context: &mut T) -> Result<(), TickMismatch> {
+        for (i, row) in self.rows.into_iter().enumerate() {
+            if let Some(setup) = row.setup {
+                setup(context);
+            }
+
+            let before = statuses(tree);
+            let status = tree.tick(row.delta, context);
+            let mut errors = vec![];
+            let mut expected = vec![];
+
+            if let Some(expected) = row.status {
+                if expected != status {
+                    errors.push(format!(
+                        "root status: expected {:?}, got {:?}",
+                        expected, status
+                    ));
+                }
+            }
+
+            if let Some(path) = row.active {
+                let active = tree.active_path();
+
+                if active != path {
+                    errors.push(format!(
+                        "active path: expected {}, got {}",
+                        path.as_deref().unwrap_or("none"),
+                        active.as_deref().unwrap_or("none")
+                    ));
+                }
+            }
+
+            for (path, status) in row.nodes {
+                match tree.find_by_path(&path) {
+                    Some(node) if node.borrow().status == status => {}
+                    Some(_) => expected.push((path, status)),
+                    None => errors.push(format!("no node at {}", path)),
+                }
+            }
+
+            if !errors.is_empty() || !expected.is_empty() {
+                for (path, status) in &expected {
+                    errors.push(format!("{}: expected {:?}", path, status));
+                }
+
+                return Err(TickMismatch {
+                    tick: i + 1,
+                    errors,
+                    tree: diff(&before, &statuses(tree), &expected),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like `run`, but panics with the mismatch.
+    pub fn assert(self, tree: &mut BehaviorTree<T>, context: &mut T) {
+        if let Err(mismatch) = self.run(tree, context) {
+            panic!("{}", mismatch);
+        }
+    }
+}
+
+/// First tick of a [`TickTable`] which didn't match, with the state of the
+/// tree after it. Lines starting with `-` are what the row expected and `+`
+/// what the node actually ended up with.
+#[derive(Debug)]
+pub struct TickMismatch {
+    /// Starting from 1.
+    pub tick: usize,
+    pub errors: Vec<String>,
+    pub tree: String,
+}
+
+impl std::fmt::Display for TickMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "tick {} didn't match:", self.tick)?;
+
+        for error in &self.errors {
+            writeln!(f, "  {}", error)?;
+        }
+
+        write!(f, "tree after tick {}:\n{}", self.tick, self.tree)
+    }
+}
+
+impl std::error::Error for TickMismatch {}
+
+impl<T> BehaviorTree<T> {
+    /// Path of the deepest running node, following running nodes down from
+    /// the root. `None` when the root isn't running.
+    pub fn active_path(&self) -> Option<String> {
+        let mut active = None;
+
+        for (path, node) in self.nodes() {
+            if node.borrow().status != Status::Running {
+                continue;
+            }
+
+            let parent_active = match (&active, path.rfind('/')) {
+                (None, None) => true,
+                (Some(active), Some(i)) => *active == path[..i],
+                _ => false,
+            };
+
+            if parent_active {
+                active = Some(path);
+            }
+        }
+
+        active
+    }
+}
+
+fn statuses<T>(tree: &BehaviorTree<T>) -> Vec<(String, Status)> {
+    tree.nodes()
+        .into_iter()
+        .map(|(path, node)| (path, node.borrow().status))
+        .collect()
+}
+
+fn diff(
+    before: &[(String, Status)],
+    after: &[(String, Status)],
+    expected: &[(String, Status)],
+) -> String {
+    let mut out = String::new();
+
+    for (path, status) in after {
+        let changed = match before.iter().find(|(p, _)| p == path) {
+            Some((_, previous)) if previous != status => format!(" (was {:?})", previous),
+            _ => String::new(),
+        };
+
+        match expected.iter().find(|(p, _)| p == path) {
+            Some((_, expected)) => {
+                let _ = writeln!(out, "- {} {:?}", path, expected);
+                let _ = writeln!(out, "+ {} {:?}{}", path, status, changed);
+            }
+            None => {
+                let _ = writeln!(out, "  {} {:?}{}", path, status, changed);
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,257 @@
+use crate::{path::NodeId, prelude::*};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+/// One visible line of the tree in a [`TreeDebugger`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeRow {
+    pub id: NodeId,
+    pub depth: usize,
+    /// `Node::name()`, which is the `collapse_as` text if there is one.
+    pub label: String,
+    pub status: Status,
+    /// Whether the node is on the path of running nodes from the root.
+    pub active: bool,
+    pub has_children: bool,
+    pub collapsed: bool,
+}
+
+/// Terminal debugger for a [`BehaviorTree`], showing the hierarchy with
+/// colour coded statuses and allowing to pause the tree and step through it
+/// tick by tick. Use [`run_debugger`] for a ready-made event loop, or drive it
+/// manually with `render`, `handle_key` and `tick` to embed it in an
+/// existing TUI.
+///
+/// Nodes built with [`Node::collapse`] start out collapsed.
+#[derive(Default)]
+pub struct TreeDebugger {
+    /// Nodes whose collapsed state differs from the default.
+    toggled: HashSet<NodeId>,
+    selected: usize,
+    paused: bool,
+    step_requested: bool,
+    ticks: u64,
+}
+
+impl TreeDebugger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Ticks the tree once more while paused.
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// Number of ticks done through the debugger.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn is_collapsed<T>(&self, node: &Node<T>) -> bool {
+        node.collapse_as.is_some() != self.toggled.contains(&node.id)
+    }
+
+    /// Ticks the tree unless it is paused and no step was requested.
+    pub fn tick<T>(
+        &mut self,
+        tree: &mut BehaviorTree<T>,
+        delta: f64,
+        context: &mut T,
+    ) -> Option<Status> {
+        if self.paused && !self.step_requested {
+            return None;
+        }
+
+        self.step_requested = false;
+        self.ticks += 1;
+        Some(tree.tick(delta, context))
+    }
+
+    /// Visible rows in depth first order, skipping children of collapsed
+    /// nodes.
+    pub fn rows<T>(&self, tree: &BehaviorTree<T>) -> Vec<TreeRow> {
+        let mut rows = vec![];
+        self.collect_rows(&tree.tree.borrow(), 0, true, &mut rows);
+        rows
+    }
+
+    fn collect_rows<T>(
+        &self,
+        node: &Node<T>,
+        depth: usize,
+        parent_active: bool,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        let active = parent_active && node.status == Status::Running;
+        let collapsed = self.is_collapsed(node);
+
+        rows.push(TreeRow {
+            id: node.id,
+            depth,
+            label: node.name(),
+            status: node.status,
+            active,
+            has_children: node.iter_children().len() > 0,
+            collapsed,
+        });
+
+        if !collapsed {
+            for child in node.iter_children() {
+                self.collect_rows(&child.borrow(), depth + 1, active, rows);
+            }
+        }
+    }
+
+    pub fn toggle_collapsed(&mut self, id: NodeId) {
+        if !self.toggled.remove(&id) {
+            self.toggled.insert(id);
+        }
+    }
+
+    /// Handles a key press, returning `false` when the debugger should quit.
+    ///
+    /// - `↑`/`k` and `↓`/`j` move the selection
+    /// - `←`/`→`/`Enter` collapse or expand the selected node
+    /// - `Space`/`p` pause or resume, `s`/`n` step one tick
+    /// - `q`/`Esc` quit
+    pub fn handle_key<T>(&mut self, key: KeyCode, tree: &BehaviorTree<T>) -> bool {
+        let rows = self.rows(tree);
+
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(rows.len().saturating_sub(1))
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Enter => {
+                if let Some(row) = rows.get(self.selected) {
+                    let expand = key != KeyCode::Left;
+
+                    if row.has_children && row.collapsed == expand {
+                        self.toggle_collapsed(row.id);
+                    }
+                }
+            }
+            KeyCode::Char(' ') | KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('s') | KeyCode::Char('n') => {
+                self.paused = true;
+                self.step();
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            _ => {}
+        }
+
+        true
+    }
+
+    pub fn render<T>(&self, tree: &BehaviorTree<T>, frame: &mut Frame, area: Rect) {
+        let rows = self.rows(tree);
+
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| {
+                let marker = match (row.has_children, row.collapsed) {
+                    (false, _) => "  ",
+                    (true, true) => "▸ ",
+                    (true, false) => "▾ ",
+                };
+
+                let mut style = Style::default().fg(status_color(row.status));
+                if row.active {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+
+                ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(row.depth)),
+                    Span::raw(marker),
+                    Span::styled(row.label.clone(), style),
+                ]))
+            })
+            .collect();
+
+        let title = format!(
+            " behavior tree · tick {}{} ",
+            self.ticks,
+            if self.paused { " · paused" } else { "" }
+        );
+
+        let [tree_area, help_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+
+        let list = List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+
+        frame.render_stateful_widget(list, tree_area, &mut state);
+        frame.render_widget(
+            Paragraph::new("↑↓ select  ←→ collapse  space pause  s step  q quit")
+                .style(Style::default().fg(Color::DarkGray)),
+            help_area,
+        );
+    }
+}
+
+fn status_color(status: Status) -> Color {
+    match status {
+        Status::Initialized => Color::Gray,
+        Status::Success => Color::Green,
+        Status::Failure => Color::Red,
+        Status::Running => Color::Yellow,
+    }
+}
+
+/// Takes over the terminal and runs the tree with a [`TreeDebugger`] until
+/// the user quits, ticking it every `delta` seconds unless paused.
+pub fn run_debugger<T>(
+    tree: &mut BehaviorTree<T>,
+    context: &mut T,
+    delta: f64,
+) -> std::io::Result<()> {
+    let mut terminal = ratatui::init();
+    let mut debugger = TreeDebugger::new();
+    let interval = Duration::from_secs_f64(delta);
+    let mut last_tick = Instant::now();
+
+    let result = (|| loop {
+        terminal.draw(|frame| debugger.render(tree, frame, frame.area()))?;
+
+        if event::poll(interval.saturating_sub(last_tick.elapsed()))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !debugger.handle_key(key.code, tree) {
+                    return Ok(());
+                }
+            }
+        }
+
+        if debugger.step_requested || last_tick.elapsed() >= interval {
+            debugger.tick(tree, delta, context);
+            last_tick = Instant::now();
+        }
+    })();
+
+    ratatui::restore();
+    result
+}
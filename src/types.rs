@@ -1,4 +1,5 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     Initialized,
     Success,
@@ -11,3 +12,59 @@ impl core::default::Default for Status {
         Self::Initialized
     }
 }
+
+/// Result a node is pinned to instead of being ticked, see
+/// [`crate::BehaviorTree::force`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Override {
+    Status(Status),
+    /// Leaves the node out, a `Sequence` or `Select` moves on to the next
+    /// child as if it wasn't there. Anywhere else it counts as a success.
+    Skip,
+}
+
+/// Error returned by a fallible action.
+pub type ActionError = Box<dyn std::error::Error>;
+
+/// Error surfaced by [`crate::BehaviorTree::try_tick`], along with the path of
+/// the action which produced it (e.g. `root/Sequence[1]/Attack`).
+#[derive(Debug)]
+pub struct TickError {
+    pub path: String,
+    pub error: ActionError,
+}
+
+impl std::fmt::Display for TickError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "action at {} failed: {}", self.path, self.error)
+    }
+}
+
+impl std::error::Error for TickError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// What a change of the observed keys can interrupt, see [`crate::Node::observe`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aborts {
+    /// Only re-evaluated when the observer is entered.
+    None,
+    /// Fails the observer and resets its child while it is running.
+    Itself,
+    /// Restarts a select at the observer when it was skipped before.
+    LowerPriority,
+    Both,
+}
+
+impl Aborts {
+    pub fn itself(self) -> bool {
+        self == Aborts::Itself || self == Aborts::Both
+    }
+
+    pub fn lower_priority(self) -> bool {
+        self == Aborts::LowerPriority || self == Aborts::Both
+    }
+}
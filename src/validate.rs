@@ -0,0 +1,125 @@
+use crate::{path::join, prelude::*};
+use std::collections::HashMap;
+
+/// Problem found by [`Node::validate`], with `path` locating the offending
+/// node (e.g. `root/Sequence[1]/Attack`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// A node that needs a name for `Node::name()` doesn't have one.
+    UnnamedNode { path: String, kind: &'static str },
+    /// A sequence or select without children, which always succeeds or fails.
+    EmptyComposite { path: String, kind: &'static str },
+    /// The same node is reachable from two places, ticking it would cause a
+    /// `BorrowMutError` or update its state twice per tick.
+    SharedChild { path: String, first_path: String },
+    /// The node is its own descendant.
+    Cycle { path: String },
+    /// A wait with a negative duration.
+    NegativeWait { path: String, time: f64 },
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::UnnamedNode { path, kind } => {
+                write!(f, "{}: {} must have a name", path, kind)
+            }
+            Diagnostic::EmptyComposite { path, kind } => {
+                write!(f, "{}: {} has no children", path, kind)
+            }
+            Diagnostic::SharedChild { path, first_path } => {
+                write!(f, "{}: node is also used at {}", path, first_path)
+            }
+            Diagnostic::Cycle { path } => write!(f, "{}: node contains itself", path),
+            Diagnostic::NegativeWait { path, time } => {
+                write!(f, "{}: wait has a negative duration {}", path, time)
+            }
+        }
+    }
+}
+
+struct Validator<T> {
+    diagnostics: Vec<Diagnostic>,
+    seen: HashMap<*const Node<T>, String>,
+    ancestors: Vec<*const Node<T>>,
+}
+
+impl<T> Validator<T> {
+    fn visit(&mut self, node: &Node<T>, path: String) {
+        let ptr = node as *const Node<T>;
+
+        if self.ancestors.contains(&ptr) {
+            self.diagnostics.push(Diagnostic::Cycle { path });
+            return;
+        }
+
+        if let Some(first_path) = self.seen.get(&ptr) {
+            self.diagnostics.push(Diagnostic::SharedChild {
+                path,
+                first_path: first_path.clone(),
+            });
+            return;
+        }
+
+        self.seen.insert(ptr, path.clone());
+
+        let kind = node.behavior.kind();
+
+        match &node.behavior {
+            Behavior::While(..) | Behavior::Observe { .. } if node.name.is_none() => {
+                self.diagnostics.push(Diagnostic::UnnamedNode {
+                    path: path.clone(),
+                    kind,
+                });
+            }
+            Behavior::Sequence(_, seq) | Behavior::Select(_, seq) if seq.is_empty() => {
+                self.diagnostics.push(Diagnostic::EmptyComposite {
+                    path: path.clone(),
+                    kind,
+                });
+            }
+            Behavior::Wait { max, .. } | Behavior::RandomWait { max, .. } if *max < 0.0 => {
+                self.diagnostics.push(Diagnostic::NegativeWait {
+                    path: path.clone(),
+                    time: *max,
+                });
+            }
+            _ => {}
+        }
+
+        self.ancestors.push(ptr);
+
+        for (child, segment) in node.iter_children().zip(node.child_segments()) {
+            let child_path = join(&path, &segment);
+
+            // Nodes which are being ticked right now can't be inspected.
+            if let Ok(child) = child.try_borrow() {
+                self.visit(&child, child_path);
+            }
+        }
+
+        self.ancestors.pop();
+    }
+}
+
+impl<T> Node<T> {
+    /// Checks the tree for mistakes that would otherwise only show up (or
+    /// silently misbehave) once it is ticked. Returns an empty list for a
+    /// valid tree.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator {
+            diagnostics: vec![],
+            seen: HashMap::new(),
+            ancestors: vec![],
+        };
+
+        validator.visit(self, "root".to_owned());
+        validator.diagnostics
+    }
+}
+
+impl<T> BehaviorTree<T> {
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        self.tree.borrow().validate()
+    }
+}
@@ -0,0 +1,231 @@
+use crate::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// Iterator over the direct children of a node, see [`Node::iter_children`].
+pub struct Children<'a, T> {
+    pair: [Option<&'a Rc<RefCell<Node<T>>>>; 2],
+    rest: std::slice::Iter<'a, Rc<RefCell<Node<T>>>>,
+}
+
+impl<'a, T> Iterator for Children<'a, T> {
+    type Item = &'a Rc<RefCell<Node<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pair[0]
+            .take()
+            .or_else(|| self.pair[1].take())
+            .or_else(|| self.rest.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.pair.iter().flatten().count() + self.rest.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Children<'a, T> {}
+
+impl<'a, T> DoubleEndedIterator for Children<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rest
+            .next_back()
+            .or_else(|| self.pair[1].take())
+            .or_else(|| self.pair[0].take())
+    }
+}
+
+impl<T> Node<T> {
+    /// Same as [`Node::children`], but without collecting them into a `Vec`.
+    pub fn iter_children(&self) -> Children<'_, T> {
+        let (pair, rest): (_, &[_]) = match &self.behavior {
+            Behavior::Cond(_, _, positive, negative) => ([Some(positive), Some(negative)], &[]),
+            Behavior::Sequence(_, seq) | Behavior::Select(_, seq) => ([None, None], seq),
+            Behavior::While(_, child) | Behavior::Observe { child, .. } => {
+                ([Some(child), None], &[])
+            }
+            Behavior::Wait { .. }
+            | Behavior::RandomWait { .. }
+            | Behavior::Action(..)
+            | Behavior::ActionSuccess(..)
+            | Behavior::FallibleAction(..)
+            | Behavior::StatefulAction(..) => ([None, None], &[]),
+        };
+
+        Children {
+            pair,
+            rest: rest.iter(),
+        }
+    }
+}
+
+macro_rules! visitor {
+    (
+        $(#[$meta:meta])*
+        $visitor:ident, $node:ty, $dispatch_enter:ident, $dispatch_leave:ident;
+        $($pat:pat => $enter:ident, $leave:ident;)*
+    ) => {
+        $(#[$meta])*
+        pub trait $visitor<T> {
+            /// Called when entering a node of a kind whose callback isn't
+            /// overridden.
+            fn enter_node(&mut self, _node: $node) {}
+            /// Called when leaving a node of a kind whose callback isn't
+            /// overridden.
+            fn leave_node(&mut self, _node: $node) {}
+
+            $(
+                fn $enter(&mut self, node: $node) {
+                    self.enter_node(node)
+                }
+
+                fn $leave(&mut self, node: $node) {
+                    self.leave_node(node)
+                }
+            )*
+        }
+
+        fn $dispatch_enter<T, V: $visitor<T> + ?Sized>(visitor: &mut V, node: $node) {
+            match node.behavior {
+                $($pat => visitor.$enter(node),)*
+            }
+        }
+
+        fn $dispatch_leave<T, V: $visitor<T> + ?Sized>(visitor: &mut V, node: $node) {
+            match node.behavior {
+                $($pat => visitor.$leave(node),)*
+            }
+        }
+    };
+}
+
+macro_rules! visitors {
+    ($($pat:pat => $enter:ident, $leave:ident;)*) => {
+        visitor! {
+            /// Callbacks for [`Node::accept`], `enter_*` is called before the
+            /// children of a node are visited and `leave_*` after.
+            Visitor, &Node<T>, enter, leave;
+            $($pat => $enter, $leave;)*
+        }
+
+        visitor! {
+            /// Like [`Visitor`], but with mutable access to the nodes, see
+            /// [`Node::accept_mut`].
+            VisitorMut, &mut Node<T>, enter_mut, leave_mut;
+            $($pat => $enter, $leave;)*
+        }
+    };
+}
+
+visitors! {
+    Behavior::Wait { .. } => enter_wait, leave_wait;
+    Behavior::RandomWait { .. } => enter_random_wait, leave_random_wait;
+    Behavior::Cond(..) => enter_cond, leave_cond;
+    Behavior::Sequence(..) => enter_sequence, leave_sequence;
+    Behavior::Select(..) => enter_select, leave_select;
+    Behavior::Action(..) => enter_action, leave_action;
+    Behavior::ActionSuccess(..) => enter_action_success, leave_action_success;
+    Behavior::FallibleAction(..) => enter_fallible_action, leave_fallible_action;
+    Behavior::StatefulAction(..) => enter_stateful_action, leave_stateful_action;
+    Behavior::While(..) => enter_while, leave_while;
+    Behavior::Observe { .. } => enter_observe, leave_observe;
+}
+
+impl<T> Node<T> {
+    /// Walks the subtree depth first, calling the visitor on every node.
+    pub fn accept<V: Visitor<T> + ?Sized>(&self, visitor: &mut V) {
+        enter(visitor, self);
+
+        for child in self.iter_children() {
+            child.borrow().accept(visitor);
+        }
+
+        leave(visitor, self);
+    }
+
+    pub fn accept_mut<V: VisitorMut<T> + ?Sized>(&mut self, visitor: &mut V) {
+        enter_mut(visitor, self);
+
+        for child in self.iter_children() {
+            child.borrow_mut().accept_mut(visitor);
+        }
+
+        leave_mut(visitor, self);
+    }
+}
+
+/// Pre-order iterator over a tree, see [`BehaviorTree::pre_order`].
+pub struct PreOrder<T> {
+    stack: Vec<Rc<RefCell<Node<T>>>>,
+}
+
+impl<T> PreOrder<T> {
+    pub fn new(root: Rc<RefCell<Node<T>>>) -> Self {
+        Self { stack: vec![root] }
+    }
+}
+
+impl<T> Iterator for PreOrder<T> {
+    type Item = Rc<RefCell<Node<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack
+            .extend(node.borrow().iter_children().rev().cloned());
+        Some(node)
+    }
+}
+
+/// Post-order iterator over a tree, see [`BehaviorTree::post_order`].
+pub struct PostOrder<T> {
+    /// Nodes on the path from the root along with the number of their
+    /// children which were already visited.
+    stack: Vec<(Rc<RefCell<Node<T>>>, usize)>,
+}
+
+impl<T> PostOrder<T> {
+    pub fn new(root: Rc<RefCell<Node<T>>>) -> Self {
+        Self {
+            stack: vec![(root, 0)],
+        }
+    }
+}
+
+impl<T> Iterator for PostOrder<T> {
+    type Item = Rc<RefCell<Node<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, visited) = self.stack.last_mut()?;
+            let child = node.borrow().iter_children().nth(*visited).cloned();
+
+            match child {
+                Some(child) => {
+                    *visited += 1;
+                    self.stack.push((child, 0));
+                }
+                None => return self.stack.pop().map(|(node, _)| node),
+            }
+        }
+    }
+}
+
+impl<T> BehaviorTree<T> {
+    pub fn accept<V: Visitor<T> + ?Sized>(&self, visitor: &mut V) {
+        self.tree.borrow().accept(visitor);
+    }
+
+    pub fn accept_mut<V: VisitorMut<T> + ?Sized>(&mut self, visitor: &mut V) {
+        self.tree.borrow_mut().accept_mut(visitor);
+    }
+
+    /// Iterates over all nodes, parents before their children. Nodes are
+    /// only borrowed while the iterator advances.
+    pub fn pre_order(&self) -> PreOrder<T> {
+        PreOrder::new(self.tree.clone())
+    }
+
+    /// Iterates over all nodes, children before their parents.
+    pub fn post_order(&self) -> PostOrder<T> {
+        PostOrder::new(self.tree.clone())
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use behavior_tree::*;
+
+/// Future which is pending for a given number of polls.
+struct Countdown(u32);
+
+impl Future for Countdown {
+    type Output = u32;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+        if self.0 == 0 {
+            Poll::Ready(42)
+        } else {
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+struct SetOnDrop(Rc<Cell<bool>>);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_async_action_polls_until_ready() {
+    let mut bt: Node<Vec<u32>> = Node::async_action("query", |_data: &mut Vec<u32>| async {
+        let value = Countdown(2).await;
+        value > 40
+    });
+
+    let mut data = vec![];
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Success);
+
+    // A new future is started after the previous one completed.
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+}
+
+#[test]
+fn test_async_action_maps_result() {
+    let mut bt: Node<()> = Node::async_action("path", |_| async { Err::<(), _>("no path") });
+
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Failure);
+}
+
+#[test]
+fn test_async_action_factory_reads_context() {
+    let mut bt: Node<u32> = Node::async_action("check", |data: &mut u32| {
+        let polls = *data;
+        async move { Countdown(polls).await == 42 }
+    });
+
+    let mut data = 0;
+    assert_eq!(bt.tick(1.0, &mut data), Status::Success);
+
+    data = 1;
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Success);
+}
+
+#[test]
+fn test_async_action_reset_drops_future() {
+    let dropped = Rc::new(Cell::new(false));
+    let started = Rc::new(RefCell::new(0));
+
+    let d = dropped.clone();
+    let s = started.clone();
+    let mut bt: Node<()> = Node::async_action("long", move |_| {
+        *s.borrow_mut() += 1;
+        let guard = SetOnDrop(d.clone());
+        async move {
+            Countdown(100).await;
+            drop(guard);
+        }
+    });
+
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+    assert_eq!(*started.borrow(), 1);
+    assert!(!dropped.get());
+
+    bt.reset();
+    assert!(dropped.get());
+
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+    assert_eq!(*started.borrow(), 2);
+}
+
+#[test]
+fn test_async_action_custom_waker() {
+    let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+
+    let action =
+        AsyncAction::new(|_: &mut ()| async { Countdown(1).await == 42 }).with_waker(waker);
+    assert_eq!(StatefulAction::<()>::wakeup(&action), None);
+
+    let mut bt = BehaviorTree::new(Node::stateful_action("wake", Box::new(action)));
+    assert_eq!(bt.update(1.0, &mut ()), Some(Status::Running));
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    // Only a notification from the host wakes the tree up.
+    assert_eq!(bt.update(1.0, &mut ()), None);
+    bt.notify();
+    assert_eq!(bt.update(1.0, &mut ()), Some(Status::Success));
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "bevy")]
+
+use behavior_tree::*;
+use bevy_app::App;
+use bevy_ecs::{component::Component, world::World};
+use bevy_time::Time;
+use std::time::Duration;
+
+#[derive(Component)]
+struct Position(i32);
+
+fn walker() -> Node<World> {
+    Node::sequence(vec![
+        Node::action_success("step", |world| {
+            world.agent_mut().get_mut::<Position>().unwrap().0 += 1;
+        }),
+        Node::wait(1.0),
+    ])
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(BehaviorTreePlugin)
+        .insert_resource(Time::<()>::default());
+    app
+}
+
+fn update(app: &mut App, delta: f64) {
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs_f64(delta));
+    app.update();
+}
+
+#[test]
+fn test_bevy_ticks_trees_with_time() {
+    let mut app = app();
+
+    let a = app
+        .world_mut()
+        .spawn((Position(0), BehaviorTreeComponent::new(walker)))
+        .id();
+    let b = app.world_mut().spawn(Position(10)).id();
+
+    update(&mut app, 0.5);
+    assert_eq!(app.world().get::<Position>(a).unwrap().0, 1);
+    assert_eq!(
+        app.world().get::<BehaviorTreeComponent>(a).unwrap().status,
+        Status::Running
+    );
+
+    // Only entities with the component are ticked.
+    assert_eq!(app.world().get::<Position>(b).unwrap().0, 10);
+
+    update(&mut app, 0.6);
+    assert_eq!(
+        app.world().get::<BehaviorTreeComponent>(a).unwrap().status,
+        Status::Success
+    );
+
+    update(&mut app, 0.5);
+    assert_eq!(app.world().get::<Position>(a).unwrap().0, 2);
+}
+
+#[test]
+fn test_bevy_tree_per_entity() {
+    let mut app = app();
+
+    let a = app
+        .world_mut()
+        .spawn((Position(0), BehaviorTreeComponent::new(walker)))
+        .id();
+    update(&mut app, 0.5);
+
+    let b = app
+        .world_mut()
+        .spawn((Position(0), BehaviorTreeComponent::new(walker)))
+        .id();
+    update(&mut app, 0.6);
+
+    // `a` finished its wait, `b` only started.
+    assert_eq!(
+        app.world().get::<BehaviorTreeComponent>(a).unwrap().status,
+        Status::Success
+    );
+    assert_eq!(
+        app.world().get::<BehaviorTreeComponent>(b).unwrap().status,
+        Status::Running
+    );
+
+    app.world_mut().despawn(a);
+    update(&mut app, 0.5);
+
+    let trees = app.world().non_send_resource::<BehaviorTrees>();
+    assert!(trees.get(a).is_none());
+    assert!(trees.get(b).is_some());
+}
+
+#[test]
+fn test_bevy_action_despawns_agent() {
+    let mut app = app();
+
+    let agent = app
+        .world_mut()
+        .spawn(BehaviorTreeComponent::new(|| {
+            Node::action_success("die", |world| {
+                let agent = world.agent();
+                world.despawn(agent);
+            })
+        }))
+        .id();
+
+    update(&mut app, 0.5);
+
+    assert!(app.world().get_entity(agent).is_err());
+    assert!(app
+        .world()
+        .non_send_resource::<BehaviorTrees>()
+        .get(agent)
+        .is_none());
+    assert!(app.world().get_resource::<CurrentAgent>().is_none());
+}
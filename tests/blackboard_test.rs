@@ -0,0 +1,141 @@
+use behavior_tree::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Vec2(f32, f32);
+
+/// Moves `position` one unit towards `target`.
+struct MoveTo;
+
+impl PortAction for MoveTo {
+    fn ports(&self) -> Vec<Port> {
+        vec![Port::input("target"), Port::inout("position")]
+    }
+
+    fn tick(&mut self, ports: &mut Ports) -> Status {
+        let target = match ports.get::<Vec2>("target") {
+            Some(target) => *target,
+            None => return Status::Failure,
+        };
+        let position = ports
+            .get::<Vec2>("position")
+            .copied()
+            .unwrap_or(Vec2(0.0, 0.0));
+
+        if position == target {
+            return Status::Success;
+        }
+
+        let step = |from: f32, to: f32| from + (to - from).clamp(-1.0, 1.0);
+        ports.set(
+            "position",
+            Vec2(step(position.0, target.0), step(position.1, target.1)),
+        );
+
+        Status::Running
+    }
+}
+
+#[test]
+fn test_blackboard_typed_entries() {
+    let mut bb = Blackboard::new();
+    bb.set("health", 30i32);
+    bb.set("name", "orc".to_string());
+
+    assert_eq!(bb.get::<i32>("health"), Some(&30));
+    assert_eq!(bb.get::<f32>("health"), None);
+    assert_eq!(bb.get::<String>("name").map(|x| x.as_str()), Some("orc"));
+
+    *bb.get_mut::<i32>("health").unwrap() -= 10;
+    assert_eq!(bb.get::<i32>("health"), Some(&20));
+
+    assert!(bb.remove("health"));
+    assert!(!bb.contains_key("health"));
+    assert_eq!(bb.get::<i32>("health"), None);
+}
+
+#[test]
+fn test_port_action_remapping() {
+    let mut bt: Node<Blackboard> = Node::sequence(vec![
+        Node::port_action("move_to_cover", MoveTo, &[("target", "cover")]),
+        Node::port_action("move_to_enemy", MoveTo, &[("target", "enemy")]),
+    ]);
+
+    let mut bb = Blackboard::new();
+    bb.set("cover", Vec2(1.0, 0.0));
+    bb.set("enemy", Vec2(1.0, 2.0));
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bb.get::<Vec2>("position"), Some(&Vec2(1.0, 0.0)));
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bb.get::<Vec2>("position"), Some(&Vec2(1.0, 1.0)));
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bb.get::<Vec2>("position"), Some(&Vec2(1.0, 2.0)));
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+}
+
+#[test]
+fn test_port_action_missing_input() {
+    let mut bt: Node<Blackboard> = Node::port_action("move", MoveTo, &[("target", "nowhere")]);
+
+    assert_eq!(bt.tick(1.0, &mut Blackboard::new()), Status::Failure);
+}
+
+#[test]
+#[should_panic]
+fn test_port_action_unknown_port() {
+    let _bt: Node<Blackboard> = Node::port_action("move", MoveTo, &[("destination", "enemy")]);
+}
+
+/// Tries to access `port` in the way given by `write`.
+struct Access {
+    port: &'static str,
+    write: bool,
+}
+
+impl PortAction for Access {
+    fn ports(&self) -> Vec<Port> {
+        vec![Port::input("target"), Port::output("position")]
+    }
+
+    fn tick(&mut self, ports: &mut Ports) -> Status {
+        if self.write {
+            ports.set(self.port, 1i32);
+        } else {
+            ports.get::<i32>(self.port);
+        }
+
+        Status::Success
+    }
+}
+
+fn access(port: &'static str, write: bool) -> Status {
+    let mut bt: Node<Blackboard> = Node::port_action("access", Access { port, write }, &[]);
+    bt.tick(1.0, &mut Blackboard::new())
+}
+
+#[test]
+fn test_port_access_follows_direction() {
+    assert_eq!(access("target", false), Status::Success);
+    assert_eq!(access("position", true), Status::Success);
+}
+
+#[test]
+#[should_panic(expected = "port target is an input and can't be written")]
+fn test_port_write_to_input() {
+    access("target", true);
+}
+
+#[test]
+#[should_panic(expected = "port position is an output and can't be read")]
+fn test_port_read_from_output() {
+    access("position", false);
+}
+
+#[test]
+#[should_panic(expected = "port health isn't declared by the action")]
+fn test_port_undeclared() {
+    access("health", false);
+}
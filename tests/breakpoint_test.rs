@@ -0,0 +1,193 @@
+#![cfg(feature = "breakpoints")]
+
+use behavior_tree::*;
+
+type Log = Vec<&'static str>;
+
+fn tree() -> BehaviorTree<Log> {
+    BehaviorTree::new(Node::select(vec![
+        Node::sequence(vec![
+            Node::action("a", |log| {
+                log.push("a");
+                Status::Success
+            }),
+            Node::action("b", |log| {
+                log.push("b");
+                Status::Failure
+            }),
+        ]),
+        Node::action("c", |log| {
+            log.push("c");
+            Status::Success
+        }),
+    ]))
+}
+
+fn paused_at(tick: DebugTick) -> (String, PauseReason) {
+    match tick {
+        DebugTick::Paused(pause) => (pause.path, pause.reason),
+        other => panic!("expected a pause, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_name_breakpoint_pauses_before_node() {
+    let mut bt = tree();
+    let mut log = vec![];
+
+    bt.add_breakpoint(Breakpoint::Name("b".to_owned()));
+
+    let (path, reason) = paused_at(bt.debug_tick(1.0, &mut log));
+    assert_eq!(path, "root/Sequence[0]/b");
+    assert_eq!(
+        reason,
+        PauseReason::Breakpoint(Breakpoint::Name("b".to_owned()))
+    );
+    assert_eq!(log, vec!["a"]);
+    assert_eq!(bt.tree.borrow().status, Status::Running);
+
+    // Resuming doesn't tick `a` again nor stop at `b` right away.
+    assert_eq!(
+        bt.debug_tick(1.0, &mut log),
+        DebugTick::Finished(Status::Success)
+    );
+    assert_eq!(log, vec!["a", "b", "c"]);
+
+    // The breakpoint is hit again on the next tick.
+    let (path, _) = paused_at(bt.debug_tick(1.0, &mut log));
+    assert_eq!(path, "root/Sequence[0]/b");
+
+    bt.remove_breakpoint(&Breakpoint::Name("b".to_owned()));
+    assert!(bt.breakpoints().is_empty());
+}
+
+#[test]
+fn test_id_breakpoint() {
+    let mut bt = tree();
+    let mut log = vec![];
+
+    let id = bt.find_by_name("c")[0].borrow().id;
+    bt.add_breakpoint(Breakpoint::Id(id));
+
+    let (path, _) = paused_at(bt.debug_tick(1.0, &mut log));
+    assert_eq!(path, "root/c");
+    assert_eq!(log, vec!["a", "b"]);
+
+    assert_eq!(
+        bt.debug_tick(1.0, &mut log),
+        DebugTick::Finished(Status::Success)
+    );
+    assert_eq!(log, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_status_breakpoint_pauses_after_transition() {
+    let mut bt = tree();
+    let mut log = vec![];
+
+    let id = bt.find_by_name("b")[0].borrow().id;
+    let breakpoint = Breakpoint::Status {
+        id: Some(id),
+        to: Status::Failure,
+    };
+    bt.add_breakpoint(breakpoint.clone());
+
+    let tick = bt.debug_tick(1.0, &mut log);
+    assert_eq!(
+        tick,
+        DebugTick::Paused(Pause {
+            id,
+            path: "root/Sequence[0]/b".to_owned(),
+            reason: PauseReason::Breakpoint(breakpoint),
+        })
+    );
+    assert_eq!(log, vec!["a", "b"]);
+    assert_eq!(bt.find_by_name("c")[0].borrow().status, Status::Initialized);
+
+    assert_eq!(
+        bt.debug_tick(1.0, &mut log),
+        DebugTick::Finished(Status::Success)
+    );
+    assert_eq!(log, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_step_mode_ticks_one_node_at_a_time() {
+    let mut bt = tree();
+    let mut log = vec![];
+
+    bt.set_step_mode(true);
+
+    let mut paths = vec![];
+    let status = loop {
+        match bt.debug_tick(1.0, &mut log) {
+            DebugTick::Paused(pause) => {
+                assert_eq!(pause.reason, PauseReason::Step);
+                paths.push(pause.path);
+            }
+            DebugTick::Finished(status) => break status,
+        }
+    };
+
+    assert_eq!(status, Status::Success);
+    assert_eq!(
+        paths,
+        vec![
+            "root/Sequence[0]",
+            "root/Sequence[0]/a",
+            "root/Sequence[0]/b",
+            "root/c",
+        ]
+    );
+    assert_eq!(log, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_regular_tick_ignores_breakpoints() {
+    let mut bt = tree();
+    let mut log = vec![];
+
+    bt.add_breakpoint(Breakpoint::Name("a".to_owned()));
+    bt.set_step_mode(true);
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "b", "c"]);
+
+    bt.clear_breakpoints();
+    assert!(bt.breakpoints().is_empty());
+}
+
+struct Nested {
+    inner: BehaviorTree<Log>,
+    log: Log,
+}
+
+#[test]
+fn test_nested_tree_ignores_outer_breakpoints() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        Node::action("nested", |nested: &mut Nested| {
+            nested.inner.tick(1.0, &mut nested.log)
+        }),
+        Node::action("b", |nested: &mut Nested| {
+            nested.log.push("outer b");
+            Status::Success
+        }),
+    ]));
+    let mut nested = Nested {
+        inner: tree(),
+        log: vec![],
+    };
+
+    // The inner tree has its own `b`, which must not stop the outer tick.
+    bt.add_breakpoint(Breakpoint::Name("b".to_owned()));
+
+    let (path, _) = paused_at(bt.debug_tick(1.0, &mut nested));
+    assert_eq!(path, "root/b");
+    assert_eq!(nested.log, vec!["a", "b", "c"]);
+
+    assert_eq!(
+        bt.debug_tick(1.0, &mut nested),
+        DebugTick::Finished(Status::Success)
+    );
+    assert_eq!(nested.log, vec!["a", "b", "c", "outer b"]);
+}
@@ -0,0 +1,109 @@
+use behavior_tree::*;
+
+#[derive(Default)]
+struct Blackboard {
+    has_target: bool,
+    ammo: i32,
+    log: Vec<&'static str>,
+}
+
+#[test]
+fn test_bt_macro_builds_nested_tree() {
+    let mut bt: Node<Blackboard> = bt! {
+        select "root" {
+            cond("has_target", |bb| bb.has_target) {
+                sequence "attack" {
+                    action("aim", |bb| {
+                        bb.log.push("aim");
+                        Status::Success
+                    }),
+                    while("has_ammo", |bb: &Blackboard| bb.ammo > 0) {
+                        action_success("fire", |bb| {
+                            bb.log.push("fire");
+                            bb.ammo -= 1;
+                        })
+                    },
+                }
+            } else {
+                wait(1.0)
+            },
+            node(YesTick::action()),
+        }
+    };
+
+    assert_eq!(bt.name(), "Select");
+    assert_eq!(bt.name.as_deref(), Some("root"));
+
+    let mut bb = Blackboard::default();
+    assert_eq!(bt.tick(0.5, &mut bb), Status::Running);
+    assert_eq!(bt.tick(0.5, &mut bb), Status::Success);
+
+    bb.has_target = true;
+    bb.ammo = 1;
+    assert_eq!(bt.tick(0.5, &mut bb), Status::Success);
+    assert_eq!(bb.log, vec!["aim", "fire"]);
+
+    // Out of ammo, the attack fails and the select falls back.
+    assert_eq!(bt.tick(0.5, &mut bb), Status::Success);
+    assert_eq!(bb.log, vec!["aim", "fire", "aim"]);
+
+    let attack = bt.children()[0].borrow().children()[0].clone();
+    assert_eq!(attack.borrow().name.as_deref(), Some("attack"));
+    assert_eq!(attack.borrow().children().len(), 2);
+}
+
+#[test]
+fn test_bt_macro_leaf_nodes() {
+    let mut bt: Node<Blackboard> = bt! {
+        sequence {
+            random_wait(0.0),
+            stateful("const", ConstAction::new(Status::Success)),
+            routine("routine", Routine::new().wait(1.0)),
+            async_action("async", |_| async { true }),
+        }
+    };
+
+    assert_eq!(bt.children().len(), 4);
+    assert_eq!(bt.tick(1.0, &mut Blackboard::default()), Status::Success);
+}
+
+#[test]
+fn test_bt_macro_blackboard_nodes() {
+    struct Copy;
+
+    impl PortAction for Copy {
+        fn ports(&self) -> Vec<Port> {
+            vec![Port::input("from"), Port::output("to")]
+        }
+
+        fn tick(&mut self, ports: &mut Ports) -> Status {
+            match ports.get::<i32>("from").copied() {
+                Some(value) => {
+                    ports.set("to", value);
+                    Status::Success
+                }
+                None => Status::Failure,
+            }
+        }
+    }
+
+    let mut bt: Node<behavior_tree::Blackboard> = bt! {
+        observe("enabled", ["enabled"], Aborts::Itself, |bb: &behavior_tree::Blackboard| bb.contains_key("enabled")) {
+            port_action("copy", Copy, [("from", "a"), ("to", "b")])
+        }
+    };
+
+    let mut bb = behavior_tree::Blackboard::new();
+    bb.set("a", 3);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+
+    bb.set("enabled", ());
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(bb.get::<i32>("b"), Some(&3));
+}
+
+#[test]
+fn test_bt_macro_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,113 @@
+use behavior_tree::*;
+use std::{cell::RefCell, rc::Rc};
+
+struct Steps {
+    taken: u32,
+    required: u32,
+}
+
+impl StatefulAction<Vec<u32>> for Steps {
+    fn tick(&mut self, data: &mut Vec<u32>) -> Status {
+        self.taken += 1;
+        data.push(self.taken);
+
+        if self.taken >= self.required {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+
+    fn reset(&mut self) {
+        self.taken = 0;
+    }
+
+    fn box_clone(&self) -> Option<Box<dyn StatefulAction<Vec<u32>>>> {
+        Some(Box::new(Steps {
+            taken: self.taken,
+            required: self.required,
+        }))
+    }
+}
+
+fn prototype() -> BehaviorTree<Vec<u32>> {
+    BehaviorTree::new(Node::sequence(vec![
+        Node::named_while_single(
+            "short",
+            Box::new(|data: &Vec<u32>| data.len() < 10),
+            Node::stateful_action(
+                "steps",
+                Box::new(Steps {
+                    taken: 0,
+                    required: 3,
+                }),
+            ),
+        ),
+        Node::wait(1.0),
+    ]))
+}
+
+#[test]
+fn test_clone_tree_is_independent() {
+    let mut bt = prototype();
+    let mut data = vec![];
+
+    bt.tick(1.0, &mut data);
+    bt.tick(1.0, &mut data);
+    assert_eq!(data, vec![1, 2]);
+
+    // The copy starts from scratch even though the original is running.
+    let mut copy = bt.clone_tree().unwrap();
+    assert_eq!(copy.tree.borrow().status, Status::Initialized);
+
+    let mut copy_data = vec![];
+    copy.tick(1.0, &mut copy_data);
+    assert_eq!(copy_data, vec![1]);
+
+    bt.tick(1.0, &mut data);
+    assert_eq!(data, vec![1, 2, 3]);
+
+    // Nodes are fresh, with the same ids and paths.
+    let original: Vec<_> = bt.nodes();
+    let copied: Vec<_> = copy.nodes();
+    assert_eq!(original.len(), copied.len());
+
+    for ((path, node), (copy_path, copy_node)) in original.iter().zip(&copied) {
+        assert_eq!(path, copy_path);
+        assert!(!Rc::ptr_eq(node, copy_node));
+        assert_eq!(node.borrow().id, copy_node.borrow().id);
+    }
+}
+
+#[test]
+fn test_clone_tree_without_hook() {
+    let bt: BehaviorTree<()> = BehaviorTree::new(Node::select(vec![
+        Node::wait(1.0),
+        Node::sequence(vec![
+            Node::action("a", |_| Status::Success),
+            AlwaysRunning::action(),
+        ]),
+        Node::stateful_action("const", Box::new(ConstAction::new(Status::Success))),
+    ]));
+
+    let err = bt.clone_tree().err().unwrap();
+    assert_eq!(err.path, "root/const");
+    assert_eq!(
+        err.to_string(),
+        "stateful action at root/const can't be cloned"
+    );
+}
+
+#[test]
+fn test_clone_shared_child() {
+    let shared = Rc::new(RefCell::new(Node::wait(1.0)));
+    let bt: BehaviorTree<()> = BehaviorTree::new(Node::cond(
+        "cond",
+        |_| true,
+        Node::named_while_single_child("a", Box::new(|_| true), shared.clone()),
+        Node::named_while_single_child("b", Box::new(|_| true), shared),
+    ));
+
+    assert_eq!(bt.validate().len(), 1);
+    assert_eq!(bt.clone_tree().unwrap().validate(), vec![]);
+}
@@ -0,0 +1,9 @@
+use behavior_tree::*;
+
+fn main() {
+    let _: Node<()> = bt! {
+        cond("ready", |_| true) {
+            wait(1.0)
+        }
+    };
+}
@@ -0,0 +1,9 @@
+use behavior_tree::*;
+
+fn main() {
+    let _: Node<()> = bt! {
+        sequnce {
+            wait(1.0),
+        }
+    };
+}
@@ -0,0 +1,10 @@
+use behavior_tree::*;
+
+fn main() {
+    let _: Node<()> = bt! {
+        sequence {
+            wait(1.0),
+            action("fire"),
+        }
+    };
+}
@@ -57,3 +57,30 @@ fn test_cond() {
     assert_eq!(bb.result, "yes");
     assert_eq!(status, Status::Success);
 }
+
+#[test]
+fn test_cond_resets_abandoned_branch() {
+    let mut bt: Node<Blackboard> = Node::cond(
+        "is_foo",
+        |data| data.is_foo,
+        Node::wait(2.0),
+        AlwaysRunning::action(),
+    );
+
+    let mut bb = Blackboard {
+        is_foo: true,
+        result: "".to_string(),
+    };
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+
+    // Switching to the other branch stops the wait instead of pausing it.
+    bb.is_foo = false;
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.children()[0].borrow().status, Status::Initialized);
+
+    // Back on the wait, it starts over rather than finishing.
+    bb.is_foo = true;
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+}
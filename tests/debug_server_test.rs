@@ -0,0 +1,275 @@
+#![cfg(feature = "debug-server")]
+
+use behavior_tree::*;
+use std::time::{Duration, Instant};
+
+fn tree() -> BehaviorTree<u32> {
+    BehaviorTree::new(Node::sequence(vec![
+        Node::action("check", |_| Status::Success),
+        Node::action("work", |count| {
+            *count += 1;
+            if *count >= 3 {
+                Status::Success
+            } else {
+                Status::Running
+            }
+        }),
+    ]))
+}
+
+fn connect(server: &mut DebugServer, bt: &mut BehaviorTree<u32>) -> DebugClient {
+    let client = DebugClient::connect(server.local_addr().unwrap()).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while server.client_count() == 0 {
+        assert!(Instant::now() < deadline, "client was never accepted");
+        server.poll(bt);
+    }
+
+    client
+}
+
+/// Sends a command and waits until the server has handled it.
+fn send(
+    client: &mut DebugClient,
+    server: &mut DebugServer,
+    bt: &mut BehaviorTree<u32>,
+    command: Command,
+) {
+    client.send(&command).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while server.poll(bt) == 0 {
+        assert!(Instant::now() < deadline, "command was never received");
+    }
+}
+
+#[test]
+fn test_tree_and_ticks_are_streamed() {
+    let mut bt = tree();
+    let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server, &mut bt);
+
+    let nodes = match client.recv().unwrap() {
+        Message::Tree {
+            tick,
+            paused,
+            nodes,
+        } => {
+            assert_eq!(tick, 0);
+            assert!(!paused);
+            nodes
+        }
+        other => panic!("unexpected {:?}", other),
+    };
+
+    let paths: Vec<&str> = nodes.iter().map(|n| n.path.as_str()).collect();
+    assert_eq!(paths, vec!["root", "root/check", "root/work"]);
+    assert_eq!(nodes[0].parent, None);
+    assert_eq!(nodes[2].parent, Some(nodes[0].id));
+    assert_eq!(nodes[2].kind, "Action");
+
+    let mut count = 0;
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), Some(Status::Running));
+
+    assert_eq!(
+        client.recv().unwrap(),
+        Message::Tick {
+            tick: 1,
+            status: Status::Running,
+            changes: vec![
+                StatusChange {
+                    id: nodes[0].id,
+                    status: Status::Running
+                },
+                StatusChange {
+                    id: nodes[1].id,
+                    status: Status::Success
+                },
+                StatusChange {
+                    id: nodes[2].id,
+                    status: Status::Running
+                },
+            ],
+        }
+    );
+
+    // Only nodes whose status changed are sent.
+    server.tick(&mut bt, 1.0, &mut count);
+    match client.recv().unwrap() {
+        Message::Tick { tick, changes, .. } => {
+            assert_eq!(tick, 2);
+            assert!(changes.is_empty());
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_pause_step_resume() {
+    let mut bt = tree();
+    let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server, &mut bt);
+    client.recv().unwrap();
+
+    let mut count = 0;
+
+    send(&mut client, &mut server, &mut bt, Command::Pause);
+    assert!(server.is_paused());
+    assert_eq!(
+        client.recv().unwrap(),
+        Message::Paused {
+            tick: 0,
+            breakpoint: None
+        }
+    );
+
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), None);
+    assert_eq!(count, 0);
+
+    send(&mut client, &mut server, &mut bt, Command::Step);
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), Some(Status::Running));
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), None);
+    assert_eq!(count, 1);
+    assert!(matches!(
+        client.recv().unwrap(),
+        Message::Tick { tick: 1, .. }
+    ));
+
+    send(&mut client, &mut server, &mut bt, Command::Resume);
+    assert_eq!(client.recv().unwrap(), Message::Resumed { tick: 1 });
+    assert!(server.tick(&mut bt, 1.0, &mut count).is_some());
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_breakpoint_pauses_after_tick() {
+    let mut bt = tree();
+    let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server, &mut bt);
+    client.recv().unwrap();
+
+    send(
+        &mut client,
+        &mut server,
+        &mut bt,
+        Command::Breakpoint {
+            path: "root/work".to_owned(),
+            status: Some(Status::Success),
+        },
+    );
+
+    let mut count = 0;
+    let mut ticks = 0;
+    while server.tick(&mut bt, 1.0, &mut count).is_some() {
+        ticks += 1;
+        assert!(ticks < 10);
+    }
+
+    assert_eq!(ticks, 3);
+    assert!(server.is_paused());
+
+    let mut messages = vec![];
+    for _ in 0..4 {
+        messages.push(client.recv().unwrap());
+    }
+    assert_eq!(
+        messages[3],
+        Message::Paused {
+            tick: 3,
+            breakpoint: Some("root/work".to_owned())
+        }
+    );
+}
+
+#[test]
+fn test_force_status() {
+    let mut bt = tree();
+    let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server, &mut bt);
+    client.recv().unwrap();
+
+    send(
+        &mut client,
+        &mut server,
+        &mut bt,
+        Command::Force {
+            path: "root/work".to_owned(),
+            status: Some(Status::Success),
+        },
+    );
+
+    let mut count = 0;
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), Some(Status::Success));
+
+    send(&mut client, &mut server, &mut bt, Command::GetTree);
+    client.recv().unwrap();
+    match client.recv().unwrap() {
+        Message::Tree { nodes, .. } => {
+            assert_eq!(nodes[2].forced, Some(Override::Status(Status::Success)))
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    send(
+        &mut client,
+        &mut server,
+        &mut bt,
+        Command::Force {
+            path: "root/work".to_owned(),
+            status: None,
+        },
+    );
+    assert_eq!(server.tick(&mut bt, 1.0, &mut count), Some(Status::Running));
+}
+
+#[test]
+fn test_invalid_commands_are_reported() {
+    let mut bt = tree();
+    let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+    let mut client = connect(&mut server, &mut bt);
+    client.recv().unwrap();
+
+    send(
+        &mut client,
+        &mut server,
+        &mut bt,
+        Command::Breakpoint {
+            path: "root/missing".to_owned(),
+            status: None,
+        },
+    );
+
+    assert_eq!(
+        client.recv().unwrap(),
+        Message::Error {
+            message: "no node at root/missing".to_owned()
+        }
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix_socket() {
+    let path = std::env::temp_dir().join(format!("behavior-tree-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut bt = tree();
+    let mut server = DebugServer::bind_unix(&path).unwrap();
+    let mut client = DebugClient::connect_unix(&path).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while server.client_count() == 0 {
+        assert!(Instant::now() < deadline);
+        server.poll(&mut bt);
+    }
+
+    assert!(matches!(client.recv().unwrap(), Message::Tree { .. }));
+    server.tick(&mut bt, 1.0, &mut 0);
+    assert!(matches!(
+        client.recv().unwrap(),
+        Message::Tick { tick: 1, .. }
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "derive")]
+
+use behavior_tree::*;
+
+#[derive(StatefulAction)]
+struct Charge {
+    #[reset = 0]
+    ticks: u32,
+    #[reset]
+    log: Vec<u32>,
+    required: u32,
+}
+
+impl Charge {
+    fn tick<T>(&mut self, _data: &mut T) -> Status {
+        self.ticks += 1;
+        self.log.push(self.ticks);
+
+        if self.ticks >= self.required {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+}
+
+struct Blackboard {
+    targets: Vec<&'static str>,
+}
+
+#[derive(StatefulAction)]
+#[action(name = "pick", context = Blackboard)]
+struct PickTarget {
+    #[reset(None)]
+    picked: Option<&'static str>,
+}
+
+impl PickTarget {
+    fn tick(&mut self, data: &mut Blackboard) -> Status {
+        match data.targets.pop() {
+            Some(target) => {
+                self.picked = Some(target);
+                Status::Success
+            }
+            None => Status::Failure,
+        }
+    }
+}
+
+#[test]
+fn test_derived_reset() {
+    let mut charge = Charge {
+        ticks: 0,
+        log: vec![],
+        required: 3,
+    };
+
+    assert_eq!(
+        StatefulAction::<()>::tick(&mut charge, &mut ()),
+        Status::Running
+    );
+    assert_eq!(
+        StatefulAction::<()>::tick(&mut charge, &mut ()),
+        Status::Running
+    );
+    assert_eq!(charge.log, vec![1, 2]);
+
+    StatefulAction::<()>::reset(&mut charge);
+    assert_eq!(charge.ticks, 0);
+    assert!(charge.log.is_empty());
+    assert_eq!(charge.required, 3);
+}
+
+#[test]
+fn test_derived_node_constructor() {
+    let mut bt: Node<()> = Charge {
+        ticks: 0,
+        log: vec![],
+        required: 2,
+    }
+    .node();
+
+    assert_eq!(bt.name(), "StatefulAction charge");
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Success);
+    // Finished nodes are reset before the next tick.
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+}
+
+#[test]
+fn test_derived_with_context() {
+    let mut bt = Node::sequence(vec![
+        PickTarget { picked: None }.node(),
+        PickTarget { picked: None }.node(),
+    ]);
+
+    assert_eq!(bt.children()[0].borrow().name(), "StatefulAction pick");
+
+    let mut bb = Blackboard {
+        targets: vec!["orc", "goblin", "troll"],
+    };
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(bb.targets, vec!["orc"]);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+}
+
+#[derive(Clone, StatefulAction)]
+#[action(clone)]
+struct Countdown {
+    #[reset = 2]
+    left: u32,
+}
+
+impl Countdown {
+    fn tick<T>(&mut self, _data: &mut T) -> Status {
+        self.left -= 1;
+
+        if self.left == 0 {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+}
+
+#[test]
+fn test_derived_clone() {
+    let mut bt = BehaviorTree::new(Countdown { left: 2 }.node());
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+
+    let mut copy = bt.clone_tree().unwrap();
+    assert_eq!(copy.tick(1.0, &mut ()), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Success);
+
+    assert!(BehaviorTree::<()>::new(
+        Charge {
+            ticks: 0,
+            log: vec![],
+            required: 1,
+        }
+        .node()
+    )
+    .clone_tree()
+    .is_err());
+}
@@ -0,0 +1,179 @@
+use behavior_tree::*;
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+};
+
+/// Tiny ECS standing in for a real backend, without a `Default` as the
+/// world is only ever borrowed.
+struct MockWorld {
+    entities: HashSet<u32>,
+    components: HashMap<(u32, TypeId), Box<dyn Any + Send + Sync>>,
+}
+
+impl MockWorld {
+    fn new() -> Self {
+        Self {
+            entities: HashSet::new(),
+            components: HashMap::new(),
+        }
+    }
+
+    fn spawn(&mut self, id: u32) -> u32 {
+        self.entities.insert(id);
+        id
+    }
+}
+
+impl EcsWorld for MockWorld {
+    type Entity = u32;
+
+    fn contains(&self, entity: u32) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    fn with<C: Send + Sync + 'static, R>(&self, entity: u32, f: impl FnOnce(&C) -> R) -> Option<R> {
+        self.components
+            .get(&(entity, TypeId::of::<C>()))
+            .and_then(|c| c.downcast_ref())
+            .map(f)
+    }
+
+    fn with_mut<C: Send + Sync + 'static, R>(
+        &mut self,
+        entity: u32,
+        f: impl FnOnce(&mut C) -> R,
+    ) -> Option<R> {
+        self.components
+            .get_mut(&(entity, TypeId::of::<C>()))
+            .and_then(|c| c.downcast_mut())
+            .map(f)
+    }
+
+    fn insert<C: Send + Sync + 'static>(&mut self, entity: u32, component: C) -> bool {
+        if !self.contains(entity) {
+            return false;
+        }
+
+        self.components
+            .insert((entity, TypeId::of::<C>()), Box::new(component));
+        true
+    }
+
+    fn remove<C: Send + Sync + 'static>(&mut self, entity: u32) -> Option<C> {
+        self.components
+            .remove(&(entity, TypeId::of::<C>()))
+            .and_then(|c| c.downcast().ok())
+            .map(|c| *c)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Health(i32);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Fleeing;
+
+fn tree() -> BehaviorTree<EcsContext<MockWorld>> {
+    BehaviorTree::new(Node::cond(
+        "fleeing",
+        has_component::<Fleeing, _>,
+        Node::component_action("heal", |health: &mut Health| {
+            health.0 += 5;
+            Status::Success
+        }),
+        Node::component_while(
+            "healthy",
+            |health: &Health| health.0 > 10,
+            Node::action("hurt", |ctx: &mut EcsContext<MockWorld>| {
+                ctx.with_mut(|health: &mut Health| health.0 -= 10);
+
+                if ctx.get::<Health>().unwrap().0 <= 10 {
+                    ctx.insert(Fleeing);
+                }
+
+                Status::Running
+            }),
+        ),
+    ))
+}
+
+#[test]
+fn test_ecs_context() {
+    let mut world = MockWorld::new();
+    let a = world.spawn(1);
+    let b = world.spawn(2);
+    world.insert(a, Health(30));
+    world.insert(b, Health(15));
+
+    // Each entity gets its own tree, the world is shared.
+    let mut tree_a = tree();
+    let mut tree_b = tree();
+
+    assert_eq!(tree_a.tick_entity(1.0, &mut world, a), Status::Running);
+    assert_eq!(tree_b.tick_entity(1.0, &mut world, b), Status::Running);
+
+    assert_eq!(world.with(a, |h: &Health| h.0), Some(20));
+    assert_eq!(world.with(b, |h: &Health| h.0), Some(5));
+    assert!(world.with(b, |_: &Fleeing| ()).is_some());
+
+    // `b` got below the threshold and flees.
+    assert_eq!(tree_b.tick_entity(1.0, &mut world, b), Status::Success);
+    assert_eq!(world.with(b, |h: &Health| h.0), Some(10));
+}
+
+#[test]
+fn test_ecs_missing_component() {
+    let mut world = MockWorld::new();
+    let entity = world.spawn(1);
+    world.insert(entity, Fleeing);
+
+    let mut bt = tree();
+    assert_eq!(bt.tick_entity(1.0, &mut world, entity), Status::Failure);
+
+    world.remove::<Fleeing>(entity);
+    assert_eq!(bt.tick_entity(1.0, &mut world, entity), Status::Failure);
+}
+
+#[test]
+fn test_ecs_world_returned_after_panic() {
+    let mut world = MockWorld::new();
+    let entity = world.spawn(1);
+
+    let mut bt = BehaviorTree::new(Node::action("panic", |_: &mut EcsContext<MockWorld>| {
+        panic!("boom")
+    }));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        bt.tick_entity(1.0, &mut world, entity)
+    }));
+
+    assert!(result.is_err());
+    assert!(world.contains(entity));
+}
+
+#[test]
+fn test_ecs_context_borrows_whole_world() {
+    let mut world = MockWorld::new();
+    let a = world.spawn(1);
+    let b = world.spawn(2);
+    world.insert(a, Health(30));
+    world.insert(b, Health(5));
+
+    let mut bt = BehaviorTree::new(Node::action(
+        "drain",
+        |ctx: &mut EcsContext<MockWorld>| match ctx.world_mut().remove::<Health>(2) {
+            Some(Health(drained)) => {
+                ctx.with_mut(|health: &mut Health| health.0 += drained);
+                Status::Success
+            }
+            None => Status::Failure,
+        },
+    ));
+
+    assert_eq!(bt.tick_entity(1.0, &mut world, a), Status::Success);
+    assert_eq!(world.with(a, |h: &Health| h.0), Some(35));
+    assert!(world.with(b, |_: &Health| ()).is_none());
+
+    assert_eq!(bt.tick_entity(1.0, &mut world, a), Status::Failure);
+}
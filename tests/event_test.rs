@@ -0,0 +1,99 @@
+use std::{cell::RefCell, rc::Rc};
+
+use behavior_tree::*;
+
+/// Action that keeps running until `done` is set from outside and only asks
+/// to be ticked when notified.
+struct WaitForSignal {
+    done: Rc<RefCell<bool>>,
+    ticks: Rc<RefCell<i32>>,
+}
+
+impl StatefulAction<()> for WaitForSignal {
+    fn tick(&mut self, _data: &mut ()) -> Status {
+        *self.ticks.borrow_mut() += 1;
+
+        if *self.done.borrow() {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+
+    fn reset(&mut self) {}
+
+    fn wakeup(&self) -> Option<f64> {
+        None
+    }
+}
+
+#[test]
+fn test_update_sleeps_until_wait_elapses() {
+    let mut bt: BehaviorTree<()> = BehaviorTree::new(Node::sequence(vec![
+        Node::wait(1.0),
+        AlwaysRunning::action(),
+    ]));
+
+    assert_eq!(bt.next_wakeup(), Some(0.0));
+    assert_eq!(bt.update(0.25, &mut ()), Some(Status::Running));
+    assert_eq!(bt.next_wakeup(), Some(0.75));
+
+    assert_eq!(bt.update(0.25, &mut ()), None);
+    assert_eq!(bt.update(0.25, &mut ()), None);
+    assert_eq!(bt.next_wakeup(), Some(0.25));
+
+    // The accumulated time is passed to the wait once it runs out.
+    assert_eq!(bt.update(0.25, &mut ()), Some(Status::Running));
+    match bt.tree.borrow().children()[0].borrow().behavior {
+        Behavior::Wait { curr, max: _ } => assert!(curr <= 0.0),
+        _ => panic!("Unexpected behavior type."),
+    }
+
+    // Plain actions have to be ticked every update.
+    assert_eq!(bt.next_wakeup(), Some(0.0));
+    assert_eq!(bt.update(0.1, &mut ()), Some(Status::Running));
+}
+
+#[test]
+fn test_update_waits_for_notify() {
+    let done = Rc::new(RefCell::new(false));
+    let ticks = Rc::new(RefCell::new(0));
+
+    let mut bt: BehaviorTree<()> = BehaviorTree::new(Node::stateful_action(
+        "signal",
+        Box::new(WaitForSignal {
+            done: done.clone(),
+            ticks: ticks.clone(),
+        }),
+    ));
+
+    assert_eq!(bt.update(0.1, &mut ()), Some(Status::Running));
+    assert_eq!(bt.next_wakeup(), None);
+
+    for _ in 0..10 {
+        assert_eq!(bt.update(0.1, &mut ()), None);
+    }
+    assert_eq!(*ticks.borrow(), 1);
+
+    *done.borrow_mut() = true;
+    bt.notify();
+    assert_eq!(bt.next_wakeup(), Some(0.0));
+    assert_eq!(bt.update(0.1, &mut ()), Some(Status::Success));
+    assert_eq!(*ticks.borrow(), 2);
+
+    // A finished tree stays idle until notified again.
+    assert_eq!(bt.next_wakeup(), None);
+    assert_eq!(bt.update(0.1, &mut ()), None);
+}
+
+#[test]
+fn test_next_wakeup_uses_running_branch() {
+    let mut bt: Node<()> = Node::select(vec![
+        Node::action("fail", |_| Status::Failure),
+        Node::sequence(vec![Node::wait(0.5), Node::wait(3.0)]),
+    ]);
+
+    assert_eq!(bt.next_wakeup(), None);
+    assert_eq!(bt.tick(0.2, &mut ()), Status::Running);
+    assert!((bt.next_wakeup().unwrap() - 0.3).abs() < 1e-9);
+}
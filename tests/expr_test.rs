@@ -0,0 +1,187 @@
+use behavior_tree::*;
+
+fn blackboard() -> Blackboard {
+    let mut bb = Blackboard::new();
+    bb.set("health", 25i32);
+    bb.set("ammo", 3u32);
+    bb.set("speed", 1.5f64);
+    bb.set("alert", false);
+    bb.set("state", "idle".to_owned());
+    bb.set("target", "orc");
+    bb.set("inventory", vec![1, 2, 3]);
+    bb
+}
+
+fn eval(source: &str) -> Result<Value, ExprError> {
+    Expression::parse(source)?.eval(&blackboard())
+}
+
+#[test]
+fn test_expr_eval() {
+    let cases = [
+        ("health < 30 && ammo > 0", Value::Bool(true)),
+        ("health < 30 && !(ammo > 0)", Value::Bool(false)),
+        ("alert || state == 'idle'", Value::Bool(true)),
+        ("1 + 2 * 3 - 4 / 2", Value::Number(5.0)),
+        ("(1 + 2) * 3 % 5", Value::Number(4.0)),
+        ("-speed * 2", Value::Number(-3.0)),
+        ("state + \"-\" + target", Value::Str("idle-orc".to_owned())),
+        ("target != \"elf\" && speed >= 1.5", Value::Bool(true)),
+        ("\"abc\" < \"abd\"", Value::Bool(true)),
+        ("true == !alert", Value::Bool(true)),
+    ];
+
+    for (source, expected) in cases.iter() {
+        assert_eq!(&eval(source).unwrap(), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_expr_short_circuit() {
+    // The missing key on the right is never looked up.
+    assert_eq!(eval("alert && missing > 1"), Ok(Value::Bool(false)));
+    assert_eq!(eval("!alert || missing > 1"), Ok(Value::Bool(true)));
+}
+
+#[test]
+fn test_expr_parse_errors() {
+    let err = Expression::parse("health < ").err().unwrap();
+    assert_eq!(err.message, "expected a value, found end of expression");
+    assert_eq!(err.position, 9);
+    assert_eq!(
+        err.to_string(),
+        "expected a value, found end of expression at column 10\n  health < \n           ^"
+    );
+
+    let err = Expression::parse("health # 2").err().unwrap();
+    assert_eq!(err.message, "unexpected character `#`");
+    assert_eq!(err.position, 7);
+
+    let err = Expression::parse("(health < 3").err().unwrap();
+    assert_eq!(err.message, "expected `)`, found end of expression");
+
+    let err = Expression::parse("health 3").err().unwrap();
+    assert_eq!(err.message, "expected an operator, found `3`");
+
+    let err = Expression::parse("state == 'idle").err().unwrap();
+    assert_eq!(err.message, "unterminated string");
+    assert_eq!(err.position, 9);
+
+    assert_eq!(
+        Expression::parse("1.2.3").err().unwrap().message,
+        "invalid number `1.2.3`"
+    );
+}
+
+#[test]
+fn test_expr_eval_errors() {
+    let err = eval("mana > 3").err().unwrap();
+    assert_eq!(err.message, "blackboard has no entry `mana`");
+    assert_eq!(err.position, 0);
+
+    let err = eval("inventory > 3").err().unwrap();
+    assert_eq!(
+        err.message,
+        "blackboard entry `inventory` has an unsupported type"
+    );
+
+    let err = eval("health + state").err().unwrap();
+    assert_eq!(err.message, "cannot apply `+` to number and string");
+    assert_eq!(err.position, 7);
+
+    assert_eq!(
+        eval("alert < true").err().unwrap().message,
+        "cannot apply `<` to bool and bool"
+    );
+    assert_eq!(eval("ammo / 0").err().unwrap().message, "division by zero");
+    assert_eq!(
+        eval("!health").err().unwrap().message,
+        "cannot apply `!` to number"
+    );
+    assert_eq!(
+        Expression::parse("health + 1")
+            .unwrap()
+            .check(&blackboard())
+            .err()
+            .unwrap()
+            .message,
+        "expected the condition to be a bool, found number"
+    );
+}
+
+#[test]
+fn test_expr_keys() {
+    let expr: Expression = "health < 30 && (ammo > 0 || health > speed)"
+        .parse()
+        .unwrap();
+    assert_eq!(expr.keys(), vec!["health", "ammo", "speed"]);
+}
+
+#[test]
+fn test_expr_nodes() {
+    let mut bt = BehaviorTree::new(
+        Node::expr_cond(
+            "hurt",
+            "health < 30",
+            Node::expr_while(
+                "has_ammo",
+                "ammo > 0",
+                Node::action("shoot", |bb: &mut Blackboard| {
+                    *bb.get_mut::<u32>("ammo").unwrap() -= 1;
+                    Status::Running
+                }),
+            )
+            .unwrap(),
+            Node::action("idle", |_| Status::Success),
+        )
+        .unwrap(),
+    );
+
+    let mut bb = blackboard();
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(*bb.get::<u32>("ammo").unwrap(), 0);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+
+    bb.set("health", 100i32);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+
+    // Errors at runtime count as false.
+    bb.remove("health");
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+
+    assert!(
+        Node::<Blackboard>::expr_cond("bad", "health <", Node::wait(1.0), Node::wait(1.0)).is_err()
+    );
+}
+
+#[test]
+fn test_expr_observe() {
+    let mut bt = BehaviorTree::new(Node::select(vec![
+        Node::expr_observe(
+            "alerted",
+            "alert && health > 10",
+            Aborts::LowerPriority,
+            AlwaysRunning::action(),
+        )
+        .unwrap(),
+        AlwaysRunning::action(),
+    ]));
+
+    let mut bb = blackboard();
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(
+        bt.tree.borrow().children()[0].borrow().status,
+        Status::Failure
+    );
+
+    bb.set("alert", true);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(
+        bt.tree.borrow().children()[0].borrow().status,
+        Status::Running
+    );
+}
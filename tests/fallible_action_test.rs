@@ -0,0 +1,102 @@
+use behavior_tree::*;
+
+#[derive(Default)]
+struct World {
+    entity: Option<u32>,
+    path_found: bool,
+    log: Vec<&'static str>,
+}
+
+fn attack(world: &mut World) -> Result<Status, ActionError> {
+    let entity = world.entity.ok_or("missing entity")?;
+    world.log.push("attack");
+
+    if entity > 0 {
+        Ok(Status::Success)
+    } else {
+        Ok(Status::Failure)
+    }
+}
+
+fn walk(world: &mut World) -> Result<Status, ActionError> {
+    let steps: u32 = "x".parse().map_err(|_| "invalid path")?;
+    world.path_found = steps > 0;
+    Ok(Status::Success)
+}
+
+#[test]
+fn test_fallible_action_success_and_failure() {
+    let mut bt = BehaviorTree::new(Node::fallible_action("attack", attack));
+    let mut world = World {
+        entity: Some(1),
+        ..Default::default()
+    };
+
+    assert_eq!(bt.try_tick(1.0, &mut world).unwrap(), Status::Success);
+
+    // Ordinary behavioural failure isn't an error.
+    world.entity = Some(0);
+    assert_eq!(bt.try_tick(1.0, &mut world).unwrap(), Status::Failure);
+}
+
+#[test]
+fn test_fallible_action_error_has_path() {
+    let mut bt = BehaviorTree::new(Node::select(vec![
+        Node::action("fail", |_| Status::Failure),
+        Node::sequence(vec![
+            Node::action_success("log", |world: &mut World| world.log.push("log")),
+            Node::fallible_action("attack", attack),
+        ]),
+        Node::action_success("flee", |world: &mut World| world.log.push("flee")),
+    ]));
+
+    let mut world = World::default();
+    let err = bt.try_tick(1.0, &mut world).unwrap_err();
+
+    assert_eq!(err.path, "root/Sequence[1]/attack");
+    assert_eq!(err.error.to_string(), "missing entity");
+    assert_eq!(
+        err.to_string(),
+        "action at root/Sequence[1]/attack failed: missing entity"
+    );
+
+    // The error made the action fail, so the select carried on.
+    assert_eq!(world.log, vec!["log", "flee"]);
+    assert_eq!(bt.tree.borrow().status, Status::Success);
+
+    // The error was consumed by the previous tick.
+    world.entity = Some(1);
+    assert_eq!(bt.try_tick(1.0, &mut world).unwrap(), Status::Success);
+}
+
+#[test]
+fn test_fallible_action_plain_tick() {
+    let mut bt: Node<World> = Node::fallible_action("walk", walk);
+    let mut world = World::default();
+
+    assert_eq!(bt.tick(1.0, &mut world), Status::Failure);
+    assert!(!world.path_found);
+    assert_eq!(bt.name(), "FallibleAction walk");
+}
+
+#[test]
+fn test_fallible_action_error_from_plain_tick_isnt_reported() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        Node::fallible_action("attack", attack),
+        Node::wait(1.0),
+    ]));
+    let mut world = World::default();
+
+    assert_eq!(bt.tick(1.0, &mut world), Status::Failure);
+
+    // The action isn't reached by the next tick, its old error is stale.
+    bt.tree.borrow_mut().move_child(0, 1).unwrap();
+    assert_eq!(bt.try_tick(0.5, &mut world).unwrap(), Status::Running);
+
+    assert_eq!(bt.tick(0.5, &mut world), Status::Failure);
+    assert_eq!(bt.try_tick(0.5, &mut world).unwrap(), Status::Running);
+    assert_eq!(
+        bt.try_tick(0.5, &mut world).unwrap_err().path,
+        "root/attack"
+    );
+}
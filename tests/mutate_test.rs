@@ -0,0 +1,204 @@
+use behavior_tree::*;
+
+type Log = Vec<&'static str>;
+
+fn step(name: &'static str) -> Node<Log> {
+    match name {
+        "a" => Node::action("a", |log| {
+            log.push("a");
+            Status::Success
+        }),
+        "b" => Node::action("b", |log| {
+            log.push("b");
+            Status::Success
+        }),
+        "alarm" => Node::action("alarm", |log| {
+            log.push("alarm");
+            Status::Success
+        }),
+        _ => unreachable!(),
+    }
+}
+
+fn current(node: &Node<Log>) -> usize {
+    match node.behavior {
+        Behavior::Sequence(current, _) | Behavior::Select(current, _) => current,
+        _ => panic!("not a composite"),
+    }
+}
+
+#[test]
+fn test_insert_before_running_child() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![step("a"), AlwaysRunning::action()]));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+    assert_eq!(log, vec!["a"]);
+
+    bt.tree.borrow_mut().insert_child(0, step("alarm")).unwrap();
+    assert_eq!(current(&bt.tree.borrow()), 2);
+
+    // The running child keeps running, the new step only runs on restart.
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+    assert_eq!(log, vec!["a"]);
+
+    bt.tree.borrow_mut().reset();
+    bt.tick(1.0, &mut log);
+    assert_eq!(log, vec!["a", "alarm", "a"]);
+}
+
+#[test]
+fn test_insert_after_running_child() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![step("a"), Node::wait(1.5), step("b")]));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+
+    bt.tree.borrow_mut().insert_child(2, step("alarm")).unwrap();
+    assert_eq!(current(&bt.tree.borrow()), 1);
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "alarm", "b"]);
+}
+
+#[test]
+fn test_remove_running_child() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        step("a"),
+        AlwaysRunning::action(),
+        step("b"),
+    ]));
+    let mut log = vec![];
+
+    bt.tick(1.0, &mut log);
+
+    let removed = bt.tree.borrow_mut().remove_child(1).unwrap();
+    assert_eq!(removed.borrow().status, Status::Initialized);
+    assert_eq!(current(&bt.tree.borrow()), 1);
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "b"]);
+}
+
+#[test]
+fn test_remove_last_running_child_restarts() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![step("a"), AlwaysRunning::action()]));
+    let mut log = vec![];
+
+    bt.tick(1.0, &mut log);
+    bt.tree.borrow_mut().remove_child(1).unwrap();
+
+    assert_eq!(bt.tree.borrow().status, Status::Initialized);
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "a"]);
+}
+
+#[test]
+fn test_move_child() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        step("a"),
+        AlwaysRunning::action(),
+        step("b"),
+    ]));
+    let mut log = vec![];
+
+    bt.tick(1.0, &mut log);
+
+    // Moving a finished step after the running one makes it run again.
+    bt.tree.borrow_mut().move_child(0, 2).unwrap();
+    assert_eq!(current(&bt.tree.borrow()), 0);
+
+    // `b` now comes before the running child and is treated as finished.
+    bt.tree.borrow_mut().move_child(1, 0).unwrap();
+    assert_eq!(current(&bt.tree.borrow()), 1);
+
+    bt.tree
+        .borrow_mut()
+        .replace_child(1, Node::wait(0.5))
+        .unwrap();
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "a"]);
+}
+
+#[test]
+fn test_move_running_child_forward() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![Node::wait(1.5), step("a"), step("b")]));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+    assert_eq!(
+        bt.tree.borrow_mut().move_child(0, 2),
+        Err(MutationError::RunningChild { index: 0 })
+    );
+
+    // Nothing moved, the wait finishes and the steps after it still run.
+    assert_eq!(current(&bt.tree.borrow()), 0);
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "b"]);
+}
+
+#[test]
+fn test_move_running_child_backward() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![step("a"), step("b"), Node::wait(1.5)]));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+    assert_eq!(
+        bt.tree
+            .borrow_mut()
+            .move_child(2, 0)
+            .unwrap_err()
+            .to_string(),
+        "child 2 is running and can't be moved"
+    );
+
+    // The finished steps don't run again.
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["a", "b"]);
+}
+
+#[test]
+fn test_replace_decorator_child() {
+    let mut bt = BehaviorTree::new(Node::named_while_single_child(
+        "loop",
+        Box::new(|_: &Log| true),
+        std::rc::Rc::new(std::cell::RefCell::new(AlwaysRunning::action())),
+    ));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+
+    let old = bt
+        .tree
+        .borrow_mut()
+        .replace_child(0, step("alarm"))
+        .unwrap();
+    assert_eq!(old.borrow().status, Status::Initialized);
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["alarm"]);
+}
+
+#[test]
+fn test_mutation_errors() {
+    let mut node: Node<Log> = Node::sequence(vec![step("a")]);
+
+    assert_eq!(
+        node.insert_child(2, step("b")),
+        Err(MutationError::IndexOutOfBounds { index: 2, len: 2 })
+    );
+    assert_eq!(
+        node.move_child(0, 1),
+        Err(MutationError::IndexOutOfBounds { index: 1, len: 1 })
+    );
+
+    let mut wait: Node<Log> = Node::wait(1.0);
+    assert_eq!(
+        wait.remove_child(0).err().unwrap(),
+        MutationError::NotComposite { kind: "Wait" }
+    );
+    assert_eq!(
+        wait.replace_child(0, step("a")).err().unwrap().to_string(),
+        "child index 0 is out of bounds for 0 children"
+    );
+}
@@ -0,0 +1,132 @@
+use std::{cell::RefCell, rc::Rc};
+
+use behavior_tree::*;
+
+fn counted_cond(
+    key: &'static str,
+    evaluations: Rc<RefCell<i32>>,
+) -> Box<dyn Fn(&Blackboard) -> bool> {
+    Box::new(move |bb: &Blackboard| {
+        *evaluations.borrow_mut() += 1;
+        bb.get::<bool>(key).copied().unwrap_or(false)
+    })
+}
+
+#[test]
+fn test_blackboard_records_changes() {
+    let mut bb = Blackboard::new();
+    let start = bb.revision();
+
+    bb.set("a", 1);
+    bb.set("b", 2);
+    let mid = bb.revision();
+    bb.set("a", 3);
+
+    let mut changed: Vec<_> = bb.changed_since(start).collect();
+    changed.sort();
+    assert_eq!(changed, vec!["a", "b"]);
+    assert_eq!(bb.changed_since(mid).collect::<Vec<_>>(), vec!["a"]);
+
+    assert!(bb.key_revision("a") > bb.key_revision("b"));
+    assert_eq!(bb.key_revision("c"), 0);
+}
+
+#[test]
+fn test_observe_skips_unchanged_condition() {
+    let evaluations = Rc::new(RefCell::new(0));
+    let mut bt: Node<Blackboard> = Node::observe(
+        "enemy_visible",
+        &["enemy_visible"],
+        Aborts::None,
+        counted_cond("enemy_visible", evaluations.clone()),
+        Node::action("attack", |_| Status::Success),
+    );
+
+    let mut bb = Blackboard::new();
+    bb.set("enemy_visible", true);
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(*evaluations.borrow(), 1);
+
+    // Unrelated keys don't cause a re-evaluation.
+    bb.set("ammo", 10);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(*evaluations.borrow(), 1);
+
+    bb.set("enemy_visible", false);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+    assert_eq!(*evaluations.borrow(), 2);
+}
+
+#[test]
+fn test_observe_aborts_none_keeps_running() {
+    let mut bt: Node<Blackboard> = Node::observe(
+        "enemy_visible",
+        &["enemy_visible"],
+        Aborts::None,
+        counted_cond("enemy_visible", Default::default()),
+        AlwaysRunning::action(),
+    );
+
+    let mut bb = Blackboard::new();
+    bb.set("enemy_visible", true);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+
+    bb.set("enemy_visible", false);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+}
+
+#[test]
+fn test_observe_aborts_self() {
+    let mut bt: Node<Blackboard> = Node::observe(
+        "enemy_visible",
+        &["enemy_visible"],
+        Aborts::Itself,
+        counted_cond("enemy_visible", Default::default()),
+        AlwaysRunning::action(),
+    );
+
+    let mut bb = Blackboard::new();
+    bb.set("enemy_visible", true);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+
+    bb.set("enemy_visible", false);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+    assert_eq!(bt.children()[0].borrow().status, Status::Initialized);
+}
+
+#[test]
+fn test_observe_aborts_lower_priority() {
+    let evaluations = Rc::new(RefCell::new(0));
+
+    let mut bt: Node<Blackboard> = Node::select(vec![
+        Node::observe(
+            "enemy_visible",
+            &["enemy_visible"],
+            Aborts::LowerPriority,
+            counted_cond("enemy_visible", evaluations.clone()),
+            Node::action("attack", |_| Status::Success),
+        ),
+        Node::sequence(vec![
+            Node::stateful_action("patrol", Box::new(ConstAction::new(Status::Success))),
+            AlwaysRunning::action(),
+        ]),
+    ]);
+
+    let mut bb = Blackboard::new();
+    bb.set("enemy_visible", false);
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(*evaluations.borrow(), 1);
+
+    bb.set("enemy_visible", true);
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(*evaluations.borrow(), 2);
+    // The running patrol branch was aborted.
+    assert_eq!(bt.children()[1].borrow().status, Status::Initialized);
+}
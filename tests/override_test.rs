@@ -0,0 +1,134 @@
+use behavior_tree::*;
+
+type Log = Vec<&'static str>;
+
+fn action(name: &'static str, status: Status) -> Node<Log> {
+    let func: fn(&mut Log) -> Status = match status {
+        Status::Success => |log| {
+            log.push("ok");
+            Status::Success
+        },
+        Status::Failure => |log| {
+            log.push("fail");
+            Status::Failure
+        },
+        _ => |log| {
+            log.push("run");
+            Status::Running
+        },
+    };
+
+    Node::action(name, func)
+}
+
+#[test]
+fn test_force_status_by_path() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        action("a", Status::Failure),
+        action("b", Status::Success),
+    ]));
+    let mut log = vec![];
+
+    assert!(bt.force_path("root/a", Some(Override::Status(Status::Success))));
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["ok"]);
+    assert_eq!(bt.find_by_name("a")[0].borrow().status, Status::Success);
+
+    assert!(bt.force_path("root/a", None));
+    log.clear();
+    assert_eq!(bt.tick(1.0, &mut log), Status::Failure);
+    assert_eq!(log, vec!["fail"]);
+
+    assert!(!bt.force_path("root/missing", Some(Override::Skip)));
+}
+
+#[test]
+fn test_skip_in_sequence_and_select() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        action("a", Status::Success),
+        action("b", Status::Failure),
+        Node::select(vec![
+            action("c", Status::Failure),
+            action("d", Status::Success),
+            action("e", Status::Success),
+        ]),
+    ]));
+    let mut log = vec![];
+
+    let b = bt.find_by_name("b")[0].borrow().id;
+    let d = bt.find_by_name("d")[0].borrow().id;
+    assert!(bt.force(b, Some(Override::Skip)));
+    assert!(bt.force(d, Some(Override::Skip)));
+
+    // Skipping `d` makes the select try `e` rather than succeed.
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["ok", "fail", "ok"]);
+    assert_eq!(
+        bt.find_by_id(b).unwrap().borrow().status,
+        Status::Initialized
+    );
+
+    bt.clear_forced();
+    log.clear();
+    assert_eq!(bt.tick(1.0, &mut log), Status::Failure);
+    assert_eq!(log, vec!["ok", "fail"]);
+
+    assert!(!bt.force(NodeId::next(), None));
+}
+
+#[test]
+fn test_skip_resets_running_node() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        Node::wait(2.0),
+        action("a", Status::Success),
+    ]));
+    let mut log = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut log), Status::Running);
+
+    assert!(bt.force_path("root/Wait[0]", Some(Override::Skip)));
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["ok"]);
+
+    let wait = bt.find_by_path("root/Wait[0]").unwrap();
+    assert_eq!(wait.borrow().status, Status::Initialized);
+    assert!(matches!(
+        wait.borrow().behavior,
+        Behavior::Wait { curr, .. } if curr == 2.0
+    ));
+}
+
+#[test]
+fn test_skip_outside_composite_succeeds() {
+    let mut bt = BehaviorTree::new(Node::cond(
+        "always",
+        |_| true,
+        action("a", Status::Failure),
+        action("b", Status::Failure),
+    ));
+    let mut log = vec![];
+
+    bt.force_path("root/a", Some(Override::Skip));
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert!(log.is_empty());
+}
+
+#[cfg(feature = "breakpoints")]
+#[test]
+fn test_forced_node_pauses_like_others() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![
+        action("a", Status::Success),
+        action("b", Status::Failure),
+    ]));
+    let mut log = vec![];
+
+    bt.force_path("root/b", Some(Override::Skip));
+    bt.add_breakpoint(Breakpoint::Name("b".to_owned()));
+
+    assert!(matches!(bt.debug_tick(1.0, &mut log), DebugTick::Paused(_)));
+    assert_eq!(
+        bt.debug_tick(1.0, &mut log),
+        DebugTick::Finished(Status::Success)
+    );
+    assert_eq!(log, vec!["ok"]);
+}
@@ -0,0 +1,105 @@
+use behavior_tree::*;
+
+fn tree() -> BehaviorTree<()> {
+    BehaviorTree::new(Node::select(vec![
+        Node::sequence(vec![
+            Node::action("attack", |_| Status::Failure),
+            Node::wait(1.0),
+        ]),
+        Node::sequence(vec![
+            Node::action("patrol", |_| Status::Running),
+            Node::action("patrol", |_| Status::Running),
+        ]),
+    ]))
+}
+
+#[test]
+fn test_paths() {
+    let bt = tree();
+
+    let paths: Vec<String> = bt.nodes().into_iter().map(|(path, _)| path).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            "root",
+            "root/Sequence[0]",
+            "root/Sequence[0]/attack",
+            "root/Sequence[0]/Wait[1]",
+            "root/Sequence[1]",
+            "root/Sequence[1]/patrol[0]",
+            "root/Sequence[1]/patrol[1]",
+        ]
+    );
+}
+
+#[test]
+fn test_find_by_path() {
+    let bt = tree();
+
+    let node = bt.find_by_path("root/Sequence[1]/patrol[1]").unwrap();
+    assert_eq!(node.borrow().name.as_deref(), Some("patrol"));
+    assert!(std::rc::Rc::ptr_eq(&node, &bt.find_by_name("patrol")[1]));
+
+    assert!(bt.find_by_path("root").is_some());
+    assert!(bt.find_by_path("root/Sequence[0]/patrol").is_none());
+    assert!(bt.find_by_path("Sequence[0]").is_none());
+}
+
+#[test]
+fn test_find_by_id() {
+    let mut bt = tree();
+
+    let attack = bt.find_by_name("attack").remove(0);
+    let id = attack.borrow().id;
+
+    assert!(std::rc::Rc::ptr_eq(&bt.find_by_id(id).unwrap(), &attack));
+    assert_eq!(bt.path_of(id).unwrap(), "root/Sequence[0]/attack");
+
+    // Ids are stable across ticks and unique within the tree.
+    bt.tick(1.0, &mut ());
+    assert_eq!(attack.borrow().id, id);
+
+    let mut ids: Vec<NodeId> = bt.nodes().iter().map(|(_, n)| n.borrow().id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 7);
+
+    assert!(bt.find_by_id(NodeId::next()).is_none());
+}
+
+#[test]
+fn test_ids_are_deterministic() {
+    fn ids(bt: &BehaviorTree<()>) -> Vec<(String, NodeId)> {
+        bt.nodes()
+            .into_iter()
+            .map(|(path, node)| (path, node.borrow().id))
+            .collect()
+    }
+
+    let mut bt = tree();
+    let expected = ids(&bt);
+
+    assert_eq!(expected[0], ("root".to_owned(), NodeId(0)));
+    assert_eq!(
+        expected[6],
+        ("root/Sequence[1]/patrol[1]".to_owned(), NodeId(6))
+    );
+
+    // The same tree built again, copied or reloaded gets the same ids.
+    assert_eq!(ids(&tree()), expected);
+    assert_eq!(ids(&bt.clone_tree().unwrap()), expected);
+
+    let root = tree().tree.borrow().clone_tree().unwrap();
+    bt.hot_reload(root);
+    assert_eq!(ids(&bt), expected);
+
+    // A child inserted at runtime has a temporary id until the next build.
+    let wait = Node::wait(1.0);
+    let id = wait.id;
+    assert!(id.is_temporary());
+
+    bt.tree.borrow_mut().insert_child(2, wait).unwrap();
+    assert_eq!(bt.path_of(id).unwrap(), "root/Wait[2]");
+    assert!(!bt.find_by_name("attack")[0].borrow().id.is_temporary());
+}
@@ -0,0 +1,274 @@
+use std::{cell::RefCell, rc::Rc};
+
+use behavior_tree::*;
+
+/// Succeeds on the third tick, keeping the count in itself.
+#[derive(Default)]
+struct Charge {
+    ticks: u32,
+}
+
+impl StatefulAction<Vec<&'static str>> for Charge {
+    fn tick(&mut self, log: &mut Vec<&'static str>) -> Status {
+        self.ticks += 1;
+        log.push("charge");
+
+        if self.ticks >= 3 {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+
+    fn reset(&mut self) {
+        self.ticks = 0;
+    }
+
+    fn hand_over_state(&mut self) -> Option<Box<dyn std::any::Any>> {
+        Some(Box::new(self.ticks))
+    }
+
+    fn take_over_state(&mut self, state: Box<dyn std::any::Any>) -> bool {
+        match state.downcast::<u32>() {
+            Ok(ticks) => {
+                self.ticks = *ticks;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+fn log(name: &'static str) -> Node<Vec<&'static str>> {
+    Node::action(name, |log| {
+        log.push("step");
+        Status::Success
+    })
+}
+
+fn charge() -> Node<Vec<&'static str>> {
+    Node::stateful_action("charge", Box::new(Charge::default()))
+}
+
+#[test]
+fn test_hot_reload_keeps_matching_state() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![log("aim"), charge(), log("fire")]));
+    let mut data = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+
+    // A new step appended after the running action doesn't disturb it.
+    let report = bt.hot_reload(Node::sequence(vec![
+        log("aim"),
+        charge(),
+        log("fire"),
+        log("reload"),
+    ]));
+
+    assert_eq!(
+        report.kept,
+        vec!["root", "root/aim", "root/charge", "root/fire"]
+    );
+    assert_eq!(report.reset, vec!["root/reload"]);
+
+    data.clear();
+    assert_eq!(bt.tick(1.0, &mut data), Status::Success);
+    // Charge finished on its third tick without repeating the aim step.
+    assert_eq!(data, vec!["charge", "step", "step"]);
+}
+
+#[test]
+fn test_hot_reload_after_tree_finished() {
+    let tree = || Node::sequence(vec![log("aim"), charge(), log("fire")]);
+    let mut bt = BehaviorTree::new(tree());
+    let mut data = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut data), Status::Success);
+
+    let report = bt.hot_reload(tree());
+
+    assert_eq!(
+        report.kept,
+        vec!["root", "root/aim", "root/charge", "root/fire"]
+    );
+    assert!(report.reset.is_empty());
+
+    // The finished tree starts over, including the charge.
+    data.clear();
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+    assert_eq!(data, vec!["step", "charge"]);
+}
+
+#[test]
+fn test_hot_reload_resets_changed_branch() {
+    let mut bt = BehaviorTree::new(Node::sequence(vec![log("aim"), charge(), log("fire")]));
+    let mut data = vec![];
+
+    assert_eq!(bt.tick(1.0, &mut data), Status::Running);
+
+    // Inserting before the running child invalidates the sequence index.
+    let report = bt.hot_reload(Node::sequence(vec![
+        log("aim"),
+        log("crouch"),
+        charge(),
+        log("fire"),
+    ]));
+
+    assert!(report.kept.is_empty());
+    assert_eq!(report.reset, vec!["root"]);
+    assert_eq!(bt.tree.borrow().status, Status::Initialized);
+}
+
+#[test]
+fn test_hot_reload_wait_and_kind_change() {
+    let mut bt: BehaviorTree<()> = BehaviorTree::new(Node::select(vec![
+        Node::action("fail", |_| Status::Failure),
+        Node::sequence(vec![Node::wait(5.0), AlwaysRunning::action()]),
+    ]));
+
+    assert_eq!(bt.tick(1.0, &mut ()), Status::Running);
+
+    let report = bt.hot_reload(Node::select(vec![
+        Node::action("fail", |_| Status::Failure),
+        Node::sequence(vec![Node::wait(2.0), Node::random_wait(1.0)]),
+    ]));
+
+    assert_eq!(
+        report.kept,
+        vec![
+            "root",
+            "root/fail",
+            "root/Sequence[1]",
+            "root/Sequence[1]/Wait[0]"
+        ]
+    );
+    assert_eq!(report.reset, vec!["root/Sequence[1]/RandomWait[1]"]);
+
+    // The remaining 4s of the wait got clamped to the new duration.
+    let root = bt.tree.borrow();
+    let sequence = root.children()[1].clone();
+    let wait = sequence.borrow().children()[0].clone();
+    match wait.borrow().behavior {
+        Behavior::Wait { curr, max } => {
+            assert_eq!(curr, 2.0);
+            assert_eq!(max, 2.0);
+        }
+        _ => panic!("Unexpected behavior type."),
+    };
+}
+
+#[test]
+fn test_hot_reload_resets_actions_without_hand_over() {
+    let value = Rc::new(RefCell::new(true));
+    let (counter, count) = Counter::action::<()>(false);
+    let v = value.clone();
+
+    let mut bt = BehaviorTree::new(Node::named_while_single_child(
+        "cond",
+        Box::new(move |_| *v.borrow()),
+        counter,
+    ));
+    bt.tick(1.0, &mut ());
+    bt.tick(1.0, &mut ());
+    assert_eq!(*count.borrow(), 2);
+
+    let (new_counter, new_count) = Counter::action::<()>(false);
+    let report = bt.hot_reload(Node::named_while_single_child(
+        "cond",
+        Box::new(|_| true),
+        new_counter,
+    ));
+    assert_eq!(report.kept, vec!["root"]);
+    assert_eq!(report.reset, vec!["root/counter"]);
+
+    // The new definition runs, the old one is gone.
+    bt.tick(1.0, &mut ());
+    assert_eq!(*count.borrow(), 2);
+    assert_eq!(*new_count.borrow(), 1);
+}
+
+/// Logs its label on every tick and succeeds on the third one.
+struct Shout {
+    label: &'static str,
+    ticks: u32,
+}
+
+impl StatefulAction<Vec<&'static str>> for Shout {
+    fn tick(&mut self, log: &mut Vec<&'static str>) -> Status {
+        self.ticks += 1;
+        log.push(self.label);
+
+        if self.ticks >= 3 {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+
+    fn reset(&mut self) {
+        self.ticks = 0;
+    }
+
+    fn hand_over_state(&mut self) -> Option<Box<dyn std::any::Any>> {
+        Some(Box::new(self.ticks))
+    }
+
+    fn take_over_state(&mut self, state: Box<dyn std::any::Any>) -> bool {
+        state
+            .downcast::<u32>()
+            .map(|ticks| self.ticks = *ticks)
+            .is_ok()
+    }
+}
+
+#[test]
+fn test_hot_reload_uses_new_action_definition() {
+    let shout = |label| {
+        Node::sequence(vec![Node::stateful_action(
+            "shout",
+            Box::new(Shout { label, ticks: 0 }),
+        )])
+    };
+
+    let mut log = vec![];
+    let mut bt = BehaviorTree::new(shout("old"));
+    bt.tick(1.0, &mut log);
+    bt.tick(1.0, &mut log);
+
+    let report = bt.hot_reload(shout("new"));
+    assert_eq!(report.kept, vec!["root", "root/shout"]);
+
+    // The new action continues from the tick count of the old one.
+    assert_eq!(bt.tick(1.0, &mut log), Status::Success);
+    assert_eq!(log, vec!["old", "old", "new"]);
+}
+
+#[test]
+fn test_hot_reload_routine_keeps_step() {
+    let routine = |label: &'static str| {
+        Node::routine(
+            "routine",
+            Routine::new()
+                .then(|log: &mut Vec<&'static str>| {
+                    log.push("first");
+                    Status::Success
+                })
+                .wait(1.0)
+                .then(move |log| {
+                    log.push(label);
+                    Status::Success
+                }),
+        )
+    };
+
+    let mut log = vec![];
+    let mut bt = BehaviorTree::new(routine("old"));
+    assert_eq!(bt.tick(0.5, &mut log), Status::Running);
+
+    bt.hot_reload(routine("new"));
+    assert_eq!(bt.tick(0.5, &mut log), Status::Success);
+    assert_eq!(log, vec!["first", "new"]);
+}
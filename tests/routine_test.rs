@@ -0,0 +1,99 @@
+use behavior_tree::*;
+
+#[derive(Default)]
+struct Npc {
+    distance_to_door: i32,
+    door_open: bool,
+    log: Vec<&'static str>,
+}
+
+fn open_door_routine() -> Routine<Npc> {
+    Routine::new()
+        .then(|npc: &mut Npc| {
+            npc.log.push("walk");
+            if npc.distance_to_door > 0 {
+                npc.distance_to_door -= 1;
+                Status::Running
+            } else {
+                Status::Success
+            }
+        })
+        .wait(2.0)
+        .then(|npc: &mut Npc| {
+            npc.log.push("open");
+            npc.door_open = true;
+            Status::Success
+        })
+}
+
+#[test]
+fn test_routine_resumes_steps() {
+    let mut bt = Node::routine("open_door", open_door_routine());
+    let mut npc = Npc {
+        distance_to_door: 2,
+        ..Default::default()
+    };
+
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Running);
+    // Reached the door, the wait starts in the same tick.
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Running);
+    assert!(!npc.door_open);
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Success);
+    assert!(npc.door_open);
+
+    assert_eq!(npc.log, vec!["walk", "walk", "walk", "open"]);
+}
+
+#[test]
+fn test_routine_failure_and_restart() {
+    let mut bt: Node<Vec<i32>> = Node::routine(
+        "fail",
+        Routine::new()
+            .then(|log: &mut Vec<i32>| {
+                log.push(1);
+                Status::Success
+            })
+            .then(|log: &mut Vec<i32>| {
+                log.push(2);
+                Status::Failure
+            })
+            .then(|_: &mut Vec<i32>| panic!("never reached")),
+    );
+
+    let mut log = vec![];
+    assert_eq!(bt.tick(1.0, &mut log), Status::Failure);
+    assert_eq!(bt.tick(1.0, &mut log), Status::Failure);
+    assert_eq!(log, vec![1, 2, 1, 2]);
+}
+
+#[test]
+fn test_routine_reset() {
+    let mut routine: Routine<()> = Routine::new().wait(1.0).wait(1.0);
+
+    assert_eq!(routine.tick_delta(1.5, &mut ()), Status::Running);
+    assert_eq!(routine.current_step(), 1);
+    assert_eq!(routine.wakeup(), Some(1.0));
+
+    StatefulAction::<()>::reset(&mut routine);
+    assert_eq!(routine.current_step(), 0);
+    assert_eq!(routine.tick_delta(0.5, &mut ()), Status::Running);
+    assert_eq!(routine.wakeup(), Some(0.5));
+}
+
+#[test]
+fn test_routine_in_sequence_is_reset() {
+    let mut bt: Node<Npc> = Node::sequence(vec![
+        Node::routine("open_door", open_door_routine()),
+        Node::action("close_door", |npc| {
+            npc.door_open = false;
+            Status::Success
+        }),
+    ]);
+    let mut npc = Npc::default();
+
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Running);
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Success);
+    assert_eq!(bt.tick(1.0, &mut npc), Status::Running);
+    assert_eq!(npc.log, vec!["walk", "open", "walk"]);
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "scripting")]
+
+use behavior_tree::*;
+
+fn blackboard(health: i64, ammo: i32) -> Blackboard {
+    let mut bb = Blackboard::new();
+    bb.set("health", health);
+    bb.set("ammo", ammo);
+    bb.set("speed", 1.0f64);
+    bb.set("state", "idle".to_owned());
+    bb
+}
+
+#[test]
+fn test_script_cond() {
+    let scripts = ScriptEngine::new();
+
+    let mut bt = BehaviorTree::new(
+        scripts
+            .cond(
+                "low_health",
+                "health < 30 && ammo > 0",
+                Node::action("retreat", |bb: &mut Blackboard| {
+                    bb.set("state", "retreat".to_owned());
+                    Status::Success
+                }),
+                Node::action("fight", |bb: &mut Blackboard| {
+                    bb.set("state", "fight".to_owned());
+                    Status::Success
+                }),
+            )
+            .unwrap(),
+    );
+
+    let mut bb = blackboard(20, 3);
+    bt.tick(1.0, &mut bb);
+    assert_eq!(bb.get::<String>("state").unwrap(), "retreat");
+
+    let mut bb = blackboard(20, 0);
+    bt.tick(1.0, &mut bb);
+    assert_eq!(bb.get::<String>("state").unwrap(), "fight");
+}
+
+#[test]
+fn test_script_while_and_action() {
+    let scripts = ScriptEngine::new();
+
+    let mut bt = BehaviorTree::new(
+        scripts
+            .while_single(
+                "has_ammo",
+                "ammo > 0",
+                scripts
+                    .action(
+                        "shoot",
+                        r#"
+                            ammo -= 1;
+                            speed = 2;
+                            state = "shooting";
+                            if ammo > 0 { RUNNING } else { SUCCESS }
+                        "#,
+                    )
+                    .unwrap(),
+            )
+            .unwrap(),
+    );
+
+    let mut bb = blackboard(100, 2);
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Running);
+    assert_eq!(*bb.get::<i32>("ammo").unwrap(), 1);
+    assert_eq!(*bb.get::<f64>("speed").unwrap(), 2.0);
+    assert_eq!(bb.get::<String>("state").unwrap(), "shooting");
+
+    // Unchanged values don't count as writes.
+    let revision = bb.key_revision("health");
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Success);
+    assert_eq!(*bb.get::<i32>("ammo").unwrap(), 0);
+    assert_eq!(bb.key_revision("health"), revision);
+
+    assert_eq!(bt.tick(1.0, &mut bb), Status::Failure);
+}
+
+#[test]
+fn test_script_action_status() {
+    let scripts = ScriptEngine::new();
+    let mut bb = blackboard(100, 2);
+
+    for (script, status) in [
+        ("health += 1;", Status::Success),
+        ("health > 200", Status::Failure),
+        ("FAILURE", Status::Failure),
+        ("42", Status::Failure),
+        ("unknown_variable + 1", Status::Failure),
+    ] {
+        let mut bt = BehaviorTree::new(scripts.action("script", script).unwrap());
+        assert_eq!(bt.tick(1.0, &mut bb), status, "{}", script);
+    }
+
+    assert_eq!(*bb.get::<i64>("health").unwrap(), 101);
+}
+
+#[test]
+fn test_script_compile_errors() {
+    let scripts = ScriptEngine::new();
+
+    let err = scripts
+        .cond::<Blackboard>("broken", "health < ", Node::wait(1.0), Node::wait(1.0))
+        .err()
+        .unwrap();
+
+    assert_eq!(err.name, "broken");
+    assert!(err
+        .to_string()
+        .starts_with("script of broken failed to compile"));
+
+    // Conditions must be expressions.
+    assert!(scripts
+        .while_single::<Blackboard>("stmt", "let x = 1; x > 0", Node::wait(1.0))
+        .is_err());
+    assert!(scripts.action::<Blackboard>("action", "ammo -=").is_err());
+}
+
+#[test]
+fn test_script_custom_engine() {
+    let mut engine = behavior_tree::rhai::Engine::new();
+    engine.register_fn("double", |x: i64| x * 2);
+    let scripts = ScriptEngine::with_engine(engine);
+
+    let mut bt = BehaviorTree::new(scripts.action("double", "health = double(health)").unwrap());
+    let mut bb = blackboard(21, 0);
+
+    bt.tick(1.0, &mut bb);
+    assert_eq!(*bb.get::<i64>("health").unwrap(), 42);
+}
+
+#[test]
+fn test_script_tree_clone() {
+    let scripts = ScriptEngine::new();
+
+    let bt = BehaviorTree::new(
+        scripts
+            .cond(
+                "has_ammo",
+                "ammo > 0",
+                scripts.action("shoot", "ammo -= 1;").unwrap(),
+                scripts.action("reload", "ammo = 5;").unwrap(),
+            )
+            .unwrap(),
+    );
+
+    let mut copy = bt.clone_tree().unwrap();
+
+    let mut bb = blackboard(100, 1);
+    copy.tick(1.0, &mut bb);
+    assert_eq!(bb.get::<i32>("ammo"), Some(&0));
+    copy.tick(1.0, &mut bb);
+    assert_eq!(bb.get::<i32>("ammo"), Some(&5));
+}
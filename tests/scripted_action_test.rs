@@ -0,0 +1,102 @@
+use behavior_tree::*;
+use Status::*;
+
+#[test]
+fn test_statuses_in_order() {
+    let (node, log) = ScriptedAction::action(vec![Running, Running, Success]);
+    let mut bt = BehaviorTree::new(node);
+
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Success);
+
+    assert_eq!(log.ticks(), vec![Running, Running, Success]);
+    assert_eq!(log.reset_count(), 0);
+}
+
+#[test]
+fn test_reset_restarts_by_default() {
+    let (node, log) = ScriptedAction::action(vec![Running, Success]);
+    let mut bt = BehaviorTree::new(node);
+
+    bt.tick(1.0, &mut ());
+    bt.tick(1.0, &mut ());
+    // The finished node is reset before it's ticked again.
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+
+    assert_eq!(
+        log.calls(),
+        vec![
+            ScriptedCall::Tick(Running),
+            ScriptedCall::Tick(Success),
+            ScriptedCall::Reset,
+            ScriptedCall::Tick(Running),
+        ]
+    );
+}
+
+#[test]
+fn test_reset_can_continue() {
+    let action = ScriptedAction::new(vec![Success, Failure, Running]).on_reset(OnReset::Continue);
+    let log = action.log();
+    let mut bt = BehaviorTree::new(action.node("scripted"));
+
+    assert_eq!(bt.tick(1.0, &mut ()), Success);
+    assert_eq!(bt.tick(1.0, &mut ()), Failure);
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+    assert_eq!(log.reset_count(), 2);
+}
+
+#[test]
+fn test_on_exhausted() {
+    let mut repeat = ScriptedAction::new(vec![Running, Failure]).on_reset(OnReset::Continue);
+    let mut looping = ScriptedAction::new(vec![Running, Failure])
+        .on_reset(OnReset::Continue)
+        .on_exhausted(OnExhausted::Loop);
+    let mut fixed = ScriptedAction::new(vec![Running])
+        .on_reset(OnReset::Continue)
+        .on_exhausted(OnExhausted::Return(Success));
+
+    let ticks = |action: &mut ScriptedAction| -> Vec<Status> {
+        (0..4)
+            .map(|_| StatefulAction::<()>::tick(action, &mut ()))
+            .collect()
+    };
+
+    assert_eq!(ticks(&mut repeat), vec![Running, Failure, Failure, Failure]);
+    assert_eq!(
+        ticks(&mut looping),
+        vec![Running, Failure, Running, Failure]
+    );
+    assert_eq!(ticks(&mut fixed), vec![Running, Success, Success, Success]);
+}
+
+#[test]
+#[should_panic(expected = "after returning all 1 statuses")]
+fn test_panics_when_exhausted() {
+    let action = ScriptedAction::new(vec![Success])
+        .on_exhausted(OnExhausted::Panic)
+        .on_reset(OnReset::Continue);
+    let mut bt = BehaviorTree::new(action.node("once"));
+
+    bt.tick(1.0, &mut ());
+    bt.tick(1.0, &mut ());
+}
+
+#[test]
+fn test_sequence_resumes_running_child() {
+    let (first, first_log) = ScriptedAction::action(vec![Success]);
+    let (second, second_log) = ScriptedAction::action(vec![Running, Running, Success]);
+    let mut bt = BehaviorTree::new(Node::sequence(vec![first, second]));
+
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Running);
+    assert_eq!(bt.tick(1.0, &mut ()), Success);
+
+    // The first child isn't ticked again while the second one runs.
+    assert_eq!(first_log.tick_count(), 1);
+    assert_eq!(second_log.ticks(), vec![Running, Running, Success]);
+
+    first_log.clear();
+    assert!(first_log.calls().is_empty());
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "persistence")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use behavior_tree::*;
+
+/// Counts its ticks and succeeds on the third one.
+struct Charge {
+    ticks: Rc<RefCell<u32>>,
+}
+
+impl StatefulAction<()> for Charge {
+    fn tick(&mut self, _data: &mut ()) -> Status {
+        let mut ticks = self.ticks.borrow_mut();
+        *ticks += 1;
+
+        if *ticks >= 3 {
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+
+    fn reset(&mut self) {
+        *self.ticks.borrow_mut() = 0;
+    }
+
+    fn save_state(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!(*self.ticks.borrow()))
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        *self.ticks.borrow_mut() = serde_json::from_value(state)?;
+        Ok(())
+    }
+}
+
+fn build(ticks: Rc<RefCell<u32>>) -> BehaviorTree<()> {
+    BehaviorTree::new(Node::sequence(vec![
+        Node::action("aim", |_| Status::Success),
+        Node::stateful_action("charge", Box::new(Charge { ticks })),
+        Node::wait(2.0),
+        Node::action("fire", |_| Status::Success),
+    ]))
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let ticks = Rc::new(RefCell::new(0));
+    let mut bt = build(ticks.clone());
+
+    assert_eq!(bt.tick(0.5, &mut ()), Status::Running);
+    assert_eq!(bt.tick(0.5, &mut ()), Status::Running);
+    assert_eq!(bt.tick(0.5, &mut ()), Status::Running);
+
+    let json = serde_json::to_string(&bt.snapshot()).unwrap();
+
+    let restored_ticks = Rc::new(RefCell::new(0));
+    let mut restored = build(restored_ticks.clone());
+    let snapshot: NodeSnapshot = serde_json::from_str(&json).unwrap();
+    restored.restore(&snapshot).unwrap();
+
+    assert_eq!(*restored_ticks.borrow(), 3);
+    assert_eq!(restored.snapshot(), bt.snapshot());
+
+    // Both trees continue from the same point: 1.5s left on the wait.
+    for tree in [&mut bt, &mut restored] {
+        assert_eq!(tree.tick(1.0, &mut ()), Status::Running);
+        assert_eq!(tree.tick(1.0, &mut ()), Status::Success);
+    }
+}
+
+#[test]
+fn test_restore_structure_mismatch() {
+    let mut bt = build(Default::default());
+    bt.tick(1.0, &mut ());
+    let snapshot = bt.snapshot();
+
+    let mut other: BehaviorTree<()> = BehaviorTree::new(Node::sequence(vec![
+        Node::action("aim", |_| Status::Success),
+        Node::wait(2.0),
+        Node::action("charge", |_| Status::Success),
+        Node::action("fire", |_| Status::Success),
+    ]));
+
+    match other.restore(&snapshot) {
+        Err(SnapshotError::StructureMismatch { path, .. }) => assert_eq!(path, "root/Wait[1]"),
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    // Nothing was restored.
+    assert_eq!(other.tree.borrow().status, Status::Initialized);
+}
+
+#[test]
+fn test_restore_invalid_action_state() {
+    let mut bt = build(Default::default());
+    let mut snapshot = bt.snapshot();
+    snapshot.children[1].state = BehaviorState::StatefulAction(Some(serde_json::json!("oops")));
+
+    match bt.restore(&snapshot) {
+        Err(SnapshotError::ActionState { path, .. }) => assert_eq!(path, "root/charge"),
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_failed_restore_leaves_tree_untouched() {
+    let build = |first: &Rc<RefCell<u32>>, second: &Rc<RefCell<u32>>| {
+        BehaviorTree::new(Node::sequence(vec![
+            Node::stateful_action(
+                "first",
+                Box::new(Charge {
+                    ticks: first.clone(),
+                }),
+            ),
+            Node::stateful_action(
+                "second",
+                Box::new(Charge {
+                    ticks: second.clone(),
+                }),
+            ),
+        ]))
+    };
+
+    let mut bt = build(&Default::default(), &Default::default());
+    for _ in 0..4 {
+        bt.tick(1.0, &mut ());
+    }
+    let mut snapshot = bt.snapshot();
+    snapshot.children[1].state = BehaviorState::StatefulAction(Some(serde_json::json!("oops")));
+
+    let (first, second) = (Rc::new(RefCell::new(0)), Rc::new(RefCell::new(0)));
+    let mut other = build(&first, &second);
+    other.tick(1.0, &mut ());
+    let before = other.snapshot();
+    assert_eq!((*first.borrow(), *second.borrow()), (1, 0));
+
+    match other.restore(&snapshot) {
+        Err(SnapshotError::ActionState { path, .. }) => assert_eq!(path, "root/second"),
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    // The first action loaded its state before the second one failed, and
+    // was rolled back.
+    assert_eq!((*first.borrow(), *second.borrow()), (1, 0));
+    assert_eq!(other.snapshot(), before);
+}
+
+#[test]
+fn test_routine_snapshot() {
+    let routine = || {
+        Node::routine(
+            "routine",
+            Routine::<()>::new()
+                .wait(1.0)
+                .wait(1.0)
+                .then(|_| Status::Success),
+        )
+    };
+
+    let mut bt = routine();
+    assert_eq!(bt.tick(1.5, &mut ()), Status::Running);
+
+    let mut restored = routine();
+    restored.restore(&bt.snapshot()).unwrap();
+    assert_eq!(restored.tick(0.5, &mut ()), Status::Running);
+    assert_eq!(restored.tick(0.5, &mut ()), Status::Success);
+}
@@ -0,0 +1,142 @@
+use behavior_tree::*;
+use Status::*;
+
+struct Blackboard {
+    cond: bool,
+}
+
+fn tree() -> BehaviorTree<Blackboard> {
+    BehaviorTree::new(Node::select(vec![
+        Node::named_while_single(
+            "guard",
+            Box::new(|bb: &Blackboard| bb.cond),
+            Node::sequence(vec![Node::wait(2.0), AlwaysRunning::action()]),
+        ),
+        Node::action("fallback", |_| Success),
+    ]))
+}
+
+#[test]
+fn test_table_passes() {
+    let mut bt = tree();
+    let mut bb = Blackboard { cond: true };
+
+    TickTable::new(vec![
+        TickRow::new(1.0)
+            .expect(Running)
+            .active("root/guard/Sequence[0]/Wait[0]"),
+        TickRow::new(1.0)
+            .expect(Running)
+            .active("root/guard/Sequence[0]/running")
+            .node("root/guard/Sequence[0]/Wait[0]", Success),
+        TickRow::new(1.0)
+            .with(|bb: &mut Blackboard| bb.cond = false)
+            .expect(Success)
+            .inactive()
+            .node("root/guard", Failure)
+            .node("root/fallback", Success),
+    ])
+    .assert(&mut bt, &mut bb);
+}
+
+#[test]
+fn test_reports_first_mismatch() {
+    let mut bt = tree();
+    let mut bb = Blackboard { cond: true };
+
+    let mismatch = TickTable::new(vec![
+        TickRow::new(1.0).expect(Running),
+        TickRow::new(0.5)
+            .expect(Running)
+            .node("root/guard/Sequence[0]/Wait[0]", Success),
+        TickRow::new(1.0).expect(Failure),
+    ])
+    .run(&mut bt, &mut bb)
+    .unwrap_err();
+
+    assert_eq!(mismatch.tick, 2);
+    assert_eq!(
+        mismatch.errors,
+        vec!["root/guard/Sequence[0]/Wait[0]: expected Success"]
+    );
+    assert_eq!(
+        mismatch.to_string(),
+        "\
+tick 2 didn't match:
+  root/guard/Sequence[0]/Wait[0]: expected Success
+tree after tick 2:
+  root Running
+  root/guard Running
+  root/guard/Sequence[0] Running
+- root/guard/Sequence[0]/Wait[0] Success
++ root/guard/Sequence[0]/Wait[0] Running
+  root/guard/Sequence[0]/running Initialized
+  root/fallback Initialized
+"
+    );
+}
+
+#[test]
+fn test_reports_status_active_path_and_unknown_nodes() {
+    let mut bt = tree();
+    let mut bb = Blackboard { cond: false };
+
+    let mismatch = TickTable::new(vec![TickRow::new(1.0)
+        .expect(Running)
+        .active("root/guard")
+        .node("root/missing", Success)])
+    .run(&mut bt, &mut bb)
+    .unwrap_err();
+
+    assert_eq!(mismatch.tick, 1);
+    assert_eq!(
+        mismatch.errors,
+        vec![
+            "root status: expected Running, got Success",
+            "active path: expected root/guard, got none",
+            "no node at root/missing",
+        ]
+    );
+    assert!(mismatch
+        .tree
+        .contains("  root/fallback Success (was Initialized)\n"));
+}
+
+#[test]
+#[should_panic(expected = "tick 1 didn't match")]
+fn test_assert_panics() {
+    let mut bt = tree();
+    let mut bb = Blackboard { cond: false };
+
+    TickTable::new(vec![TickRow::new(1.0).expect(Failure)]).assert(&mut bt, &mut bb);
+}
+
+#[test]
+fn test_active_path_follows_cond() {
+    let mut bt = BehaviorTree::new(Node::cond(
+        "cond",
+        |bb: &Blackboard| bb.cond,
+        AlwaysRunning::action(),
+        Node::wait(2.0),
+    ));
+    let mut bb = Blackboard { cond: true };
+
+    TickTable::new(vec![
+        TickRow::new(1.0).expect(Running).active("root/running"),
+        TickRow::new(1.0)
+            .with(|bb: &mut Blackboard| bb.cond = false)
+            .expect(Running)
+            .active("root/Wait[1]")
+            .node("root/running", Initialized),
+        TickRow::new(1.0)
+            .with(|bb: &mut Blackboard| bb.cond = true)
+            .expect(Running)
+            .active("root/running")
+            .node("root/Wait[1]", Initialized),
+        // The wait starts over rather than finishing what's left.
+        TickRow::new(1.0)
+            .with(|bb: &mut Blackboard| bb.cond = false)
+            .expect(Running),
+    ])
+    .assert(&mut bt, &mut bb);
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "tui")]
+
+use behavior_tree::*;
+use ratatui::{backend::TestBackend, crossterm::event::KeyCode, style::Color, Terminal};
+
+fn tree() -> BehaviorTree<()> {
+    BehaviorTree::new(Node::select(vec![
+        Node::sequence(vec![
+            Node::action("check", |_| Status::Success),
+            Node::action("fail", |_| Status::Failure),
+        ])
+        .collapse("Attack"),
+        Node::sequence(vec![Node::wait(2.0), AlwaysRunning::action()]),
+    ]))
+}
+
+fn render(debugger: &TreeDebugger, bt: &BehaviorTree<()>) -> Vec<(String, Vec<Color>)> {
+    let mut terminal = Terminal::new(TestBackend::new(50, 10)).unwrap();
+    terminal
+        .draw(|frame| debugger.render(bt, frame, frame.area()))
+        .unwrap();
+
+    let buffer = terminal.backend().buffer().clone();
+    (0..buffer.area.height)
+        .map(|y| {
+            let cells: Vec<_> = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].clone())
+                .collect();
+            (
+                cells
+                    .iter()
+                    .map(|cell| cell.symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_owned(),
+                cells.iter().map(|cell| cell.fg).collect(),
+            )
+        })
+        .collect()
+}
+
+fn labels(rows: &[TreeRow]) -> Vec<(usize, &str)> {
+    rows.iter()
+        .map(|row| (row.depth, row.label.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_tui_rows_and_collapse() {
+    let bt = tree();
+    let mut debugger = TreeDebugger::new();
+
+    // Nodes with `collapse_as` start collapsed.
+    assert_eq!(
+        labels(&debugger.rows(&bt)),
+        vec![
+            (0, "Select"),
+            (1, "Attack"),
+            (1, "Sequence"),
+            (2, "Wait 2.00/2.00"),
+            (2, "Action running")
+        ]
+    );
+
+    debugger.handle_key(KeyCode::Down, &bt);
+    debugger.handle_key(KeyCode::Right, &bt);
+    assert_eq!(
+        labels(&debugger.rows(&bt))[1..4],
+        [(1, "Attack"), (2, "Action check"), (2, "Action fail")]
+    );
+
+    debugger.handle_key(KeyCode::Down, &bt);
+    debugger.handle_key(KeyCode::Down, &bt);
+    debugger.handle_key(KeyCode::Down, &bt);
+    debugger.handle_key(KeyCode::Left, &bt);
+    assert_eq!(labels(&debugger.rows(&bt)).len(), 5);
+    assert_eq!(debugger.rows(&bt)[4].label, "Sequence");
+    assert!(debugger.rows(&bt)[4].collapsed);
+
+    assert!(!debugger.handle_key(KeyCode::Char('q'), &bt));
+}
+
+#[test]
+fn test_tui_render() {
+    let mut bt = tree();
+    let mut debugger = TreeDebugger::new();
+
+    debugger.tick(&mut bt, 1.0, &mut ());
+
+    let rows = debugger.rows(&bt);
+    assert!(rows[0].active);
+    assert!(!rows[1].active);
+    assert!(rows[2].active);
+    assert!(rows[3].active);
+
+    let lines = render(&debugger, &bt);
+    let text: Vec<&str> = lines.iter().map(|(line, _)| line.as_str()).collect();
+
+    assert!(text[0].contains("tick 1"));
+    assert_eq!(
+        text[1],
+        "│▾ Select                                        │"
+    );
+    assert_eq!(
+        text[2],
+        "│  ▸ Attack                                      │"
+    );
+    assert_eq!(
+        text[3],
+        "│  ▾ Sequence                                    │"
+    );
+    assert_eq!(
+        text[4],
+        "│      Wait 1.00/2.00                            │"
+    );
+    assert!(text[9].starts_with("↑↓ select"));
+
+    let color = |line: usize, column: usize| lines[line].1[column];
+    assert_eq!(color(1, 3), Color::Yellow);
+    assert_eq!(color(2, 5), Color::Red);
+    assert_eq!(color(4, 7), Color::Yellow);
+}
+
+#[test]
+fn test_tui_active_follows_cond() {
+    let mut bt = BehaviorTree::new(Node::cond(
+        "flag",
+        |flag: &bool| *flag,
+        AlwaysRunning::action(),
+        Node::wait(2.0),
+    ));
+    let mut debugger = TreeDebugger::new();
+
+    debugger.tick(&mut bt, 1.0, &mut true);
+    debugger.tick(&mut bt, 1.0, &mut false);
+
+    let active: Vec<bool> = debugger.rows(&bt).iter().map(|row| row.active).collect();
+    assert_eq!(active, vec![true, false, true]);
+}
+
+#[test]
+fn test_tui_step() {
+    let mut bt = tree();
+    let mut debugger = TreeDebugger::new();
+
+    debugger.handle_key(KeyCode::Char(' '), &bt);
+    assert!(debugger.is_paused());
+    assert_eq!(debugger.tick(&mut bt, 1.0, &mut ()), None);
+
+    debugger.handle_key(KeyCode::Char('s'), &bt);
+    assert_eq!(debugger.tick(&mut bt, 1.0, &mut ()), Some(Status::Running));
+    assert_eq!(debugger.tick(&mut bt, 1.0, &mut ()), None);
+    assert_eq!(debugger.ticks(), 1);
+
+    assert!(render(&debugger, &bt)[0].0.contains("tick 1 · paused"));
+
+    debugger.handle_key(KeyCode::Char('p'), &bt);
+    assert!(debugger.tick(&mut bt, 1.0, &mut ()).is_some());
+}
@@ -0,0 +1,93 @@
+use std::{cell::RefCell, rc::Rc};
+
+use behavior_tree::*;
+
+#[test]
+fn test_validate_valid_tree() {
+    let bt: BehaviorTree<()> = BehaviorTree::new(Node::sequence(vec![
+        Node::wait(1.0),
+        Node::named_while_single("test", Box::new(|_| true), YesTick::action()),
+    ]));
+
+    assert_eq!(bt.validate(), vec![]);
+
+    bt.tree.borrow_mut().tick(1.0, &mut ());
+}
+
+#[test]
+fn test_validate_reports_problems() {
+    let unnamed_while = Node {
+        id: NodeId::next(),
+        name: None,
+        behavior: Behavior::While(Rc::new(|_| true), Rc::new(RefCell::new(Node::wait(1.0)))),
+        status: Status::Initialized,
+        collapse_as: None,
+        forced: None,
+    };
+
+    let bt: Node<()> = Node::select(vec![
+        Node::sequence(vec![]),
+        Node::wait(-1.0),
+        unnamed_while,
+    ]);
+
+    assert_eq!(
+        bt.validate(),
+        vec![
+            Diagnostic::EmptyComposite {
+                path: "root/Sequence[0]".to_owned(),
+                kind: "Sequence",
+            },
+            Diagnostic::NegativeWait {
+                path: "root/Wait[1]".to_owned(),
+                time: -1.0,
+            },
+            Diagnostic::UnnamedNode {
+                path: "root/While[2]".to_owned(),
+                kind: "While",
+            },
+        ]
+    );
+
+    assert_eq!(
+        bt.validate()[2].to_string(),
+        "root/While[2]: While must have a name"
+    );
+}
+
+#[test]
+fn test_validate_shared_child() {
+    let (counter, _) = Counter::action::<()>(false);
+
+    let bt: Node<()> = Node::sequence(vec![
+        Node::named_while_single_child("a", Box::new(|_| true), counter.clone()),
+        Node::named_while_single_child("b", Box::new(|_| true), counter),
+    ]);
+
+    assert_eq!(
+        bt.validate(),
+        vec![Diagnostic::SharedChild {
+            path: "root/b/counter".to_owned(),
+            first_path: "root/a/counter".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_cycle() {
+    let inner = Rc::new(RefCell::new(Node::wait(1.0)));
+    let looping = Node::named_while_single_child("loop", Box::new(|_: &()| true), inner.clone());
+    *inner.borrow_mut() = looping;
+
+    let bt = Node::named_while_single_child("root", Box::new(|_| true), inner.clone());
+
+    assert_eq!(
+        bt.validate(),
+        vec![Diagnostic::Cycle {
+            path: "root/loop/loop".to_owned(),
+        }]
+    );
+
+    // Break the cycle so that the nodes get dropped.
+    *inner.borrow_mut() = Node::wait(1.0);
+}
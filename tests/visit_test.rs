@@ -0,0 +1,149 @@
+use behavior_tree::*;
+
+fn tree() -> BehaviorTree<()> {
+    BehaviorTree::new(Node::select(vec![
+        Node::sequence(vec![
+            Node::action("attack", |_| Status::Failure),
+            Node::wait(1.0),
+        ]),
+        Node::cond(
+            "ready",
+            |_| true,
+            Node::action_success("fire", |_| ()),
+            Node::random_wait(2.0),
+        ),
+    ]))
+}
+
+fn kinds(
+    nodes: impl Iterator<Item = std::rc::Rc<std::cell::RefCell<Node<()>>>>,
+) -> Vec<&'static str> {
+    nodes.map(|node| node.borrow().behavior.kind()).collect()
+}
+
+#[test]
+fn test_iter_children() {
+    let bt = tree();
+    let root = bt.tree.borrow();
+
+    assert_eq!(root.iter_children().len(), 2);
+    assert_eq!(
+        kinds(root.iter_children().cloned()),
+        kinds(root.children().into_iter())
+    );
+
+    let cond = root.iter_children().nth(1).unwrap().borrow();
+    assert_eq!(
+        kinds(cond.iter_children().rev().cloned()),
+        vec!["RandomWait", "ActionSuccess"]
+    );
+}
+
+#[test]
+fn test_pre_and_post_order() {
+    let bt = tree();
+
+    assert_eq!(
+        kinds(bt.pre_order()),
+        vec![
+            "Select",
+            "Sequence",
+            "Action",
+            "Wait",
+            "Cond",
+            "ActionSuccess",
+            "RandomWait"
+        ]
+    );
+    assert_eq!(
+        kinds(bt.post_order()),
+        vec![
+            "Action",
+            "Wait",
+            "Sequence",
+            "ActionSuccess",
+            "RandomWait",
+            "Cond",
+            "Select"
+        ]
+    );
+}
+
+#[derive(Default)]
+struct Stats {
+    depth: usize,
+    max_depth: usize,
+    events: Vec<String>,
+    waits: usize,
+}
+
+impl Visitor<()> for Stats {
+    fn enter_node(&mut self, _node: &Node<()>) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn leave_node(&mut self, _node: &Node<()>) {
+        self.depth -= 1;
+    }
+
+    fn enter_sequence(&mut self, node: &Node<()>) {
+        self.events.push("enter sequence".to_owned());
+        self.enter_node(node);
+    }
+
+    fn leave_sequence(&mut self, node: &Node<()>) {
+        self.events.push("leave sequence".to_owned());
+        self.leave_node(node);
+    }
+
+    fn enter_action(&mut self, node: &Node<()>) {
+        self.events.push(node.name());
+        self.enter_node(node);
+    }
+
+    fn enter_wait(&mut self, node: &Node<()>) {
+        self.waits += 1;
+        self.enter_node(node);
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let bt = tree();
+    let mut stats = Stats::default();
+
+    bt.accept(&mut stats);
+
+    assert_eq!(stats.depth, 0);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.waits, 1);
+    assert_eq!(
+        stats.events,
+        vec!["enter sequence", "Action attack", "leave sequence"]
+    );
+}
+
+struct Stretch(f64);
+
+impl VisitorMut<()> for Stretch {
+    fn enter_wait(&mut self, node: &mut Node<()>) {
+        if let Behavior::Wait { max, .. } = &mut node.behavior {
+            *max *= self.0;
+        }
+    }
+}
+
+#[test]
+fn test_visitor_mut() {
+    let mut bt = tree();
+
+    bt.accept_mut(&mut Stretch(3.0));
+
+    let wait = bt.find_by_path("root/Sequence[0]/Wait[1]").unwrap();
+    let max = match wait.borrow().behavior {
+        Behavior::Wait { max, .. } => max,
+        _ => panic!("expected a wait"),
+    };
+    assert_eq!(max, 3.0);
+}